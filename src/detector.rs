@@ -34,9 +34,46 @@
 //! println!("Detected Java runtimes in multiple paths: {:?}", runtimes);
 //! ```
 
+use crate::error::{Error, ErrorKind};
+use crate::prober::{CommandProber, VersionProber};
+use crate::report::DetectionReport;
 use crate::JavaRuntime;
-use std::path::Path;
-use walkdir::WalkDir;
+use regex::Regex;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use walkdir::{DirEntry, WalkDir};
+
+/// The default maximum depth to search for Java runtimes within a path
+///
+/// It finds `<path>/*/bin/java`, like `/usr/lib/jvm/java-17-openjdk/bin/java` from `/usr/lib/jvm`.
+pub const DEFAULT_MAX_DEPTH: usize = 2;
+
+/// Maximum depths above this are likely to make detection very slow, a warning is logged for them
+pub const MAX_DEPTH_WARNING_THRESHOLD: usize = 6;
+
+/// Detects available Java runtimes within the specified path up to [`DEFAULT_MAX_DEPTH`].
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// // Finds /usr/lib/jvm/*/bin/java
+/// let runtimes = detector::detect_java_with_default_depth("/usr/lib/jvm".as_ref());
+/// println!("Detected Java runtimes: {:?}", runtimes);
+/// ```
+pub fn detect_java_with_default_depth(path: &Path) -> Vec<JavaRuntime> {
+    detect_java(path, DEFAULT_MAX_DEPTH)
+}
 
 /// Detects available Java runtimes within the specified path up to a maximum depth.
 ///
@@ -65,129 +102,3192 @@ pub fn detect_java(path: &Path, max_depth: usize) -> Vec<JavaRuntime> {
 ///
 /// The number of new Java runtimes added to the vector.
 pub fn gather_java(runtimes: &mut Vec<JavaRuntime>, path: &Path, max_depth: usize) -> usize {
-    if path.is_file() {
-        if let Some(runtime) = detect_java_bin_dir(path) {
-            runtimes.push(runtime);
-            return 1;
-        }
-    }
-
-    let entries = WalkDir::new(path)
+    DetectorConfig::new()
         .max_depth(max_depth)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(Result::ok);
-
-    let begin_count = runtimes.len();
+        .gather(runtimes, path)
+}
 
-    for entry in entries {
-        let path = entry.path();
-        if let Some(runtime) = detect_java_bin_dir(path) {
-            runtimes.push(runtime);
-        }
-    }
-    runtimes.len() - begin_count
+/// Detects available Java runtimes within the specified path, skipping the directories of the given names.
+///
+/// The excluded directories are not walked into at all, which saves time in deep scans. See [`DetectorConfig::exclude_dir`].
+///
+/// # Parameters
+///
+/// * `path`: The path to search for Java runtimes.
+/// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+/// * `exclude`: Names of directories to skip, like `node_modules`, `.git` or `WinSxS`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+/// use std::ffi::OsStr;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-excluding");
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// # for bin in [root.join("jdk-17/bin"), root.join("app/node_modules/jre/bin")] {
+/// #     fs::create_dir_all(&bin).unwrap();
+/// #     fs::write(bin.join("java"), script).unwrap();
+/// #     fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// # }
+/// // root/jdk-17/bin/java
+/// // root/app/node_modules/jre/bin/java
+/// assert_eq!(detector::detect_java(&root, 4).len(), 2);
+///
+/// let runtimes = detector::detect_java_excluding(&root, 4, &[OsStr::new("node_modules")]);
+/// assert_eq!(runtimes.len(), 1);
+/// assert_eq!(runtimes[0].get_executable(), root.join("jdk-17/bin/java"));
+/// # }
+/// ```
+pub fn detect_java_excluding(
+    path: &Path,
+    max_depth: usize,
+    exclude: &[&OsStr],
+) -> Vec<JavaRuntime> {
+    exclude
+        .iter()
+        .fold(
+            DetectorConfig::new().max_depth(max_depth),
+            |config, &name| config.exclude_dir(name),
+        )
+        .detect(path)
 }
 
-/// Detects available Java runtimes from environment variables.
+/// Detects available Java runtimes within the specified path between a minimum and a maximum depth.
 ///
-/// It searches java runtime in paths below:
+/// See [`gather_java_with_depth`]
 ///
-/// * `JAVA_HOME`
-/// * `JAVA_ROOT`
-/// * `JDK_HOME`
-/// * `JRE_HOME`
-/// * `PATH`
-pub fn detect_java_in_environments() -> Vec<JavaRuntime> {
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-min-depth/bin");
+/// # fs::create_dir_all(root.join("jdk-17/bin")).unwrap();
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// # for java in [root.join("java"), root.join("jdk-17/bin/java")] {
+/// #     fs::write(&java, script).unwrap();
+/// #     fs::set_permissions(&java, fs::Permissions::from_mode(0o755)).unwrap();
+/// # }
+/// // bin/java
+/// // bin/jdk-17/bin/java
+/// assert_eq!(detector::detect_java_min(&root, 0, 2).len(), 2);
+///
+/// let runtimes = detector::detect_java_min(&root, 1, 2);
+/// assert_eq!(runtimes.len(), 1);
+/// assert_eq!(runtimes[0].get_executable(), root.join("jdk-17/bin/java"));
+/// # }
+/// ```
+pub fn detect_java_min(path: &Path, min_depth: usize, max_depth: usize) -> Vec<JavaRuntime> {
     let mut runtimes: Vec<JavaRuntime> = vec![];
+    gather_java_with_depth(&mut runtimes, path, min_depth, max_depth);
+    runtimes
+}
 
-    let mut gather_env = |var_name: &str| {
-        if let Ok(env_java_home) = std::env::var(var_name) {
-            gather_java(&mut runtimes, env_java_home.as_ref(), 1);
-        }
-    };
-
-    gather_env("JAVA_HOME");
-    gather_env("JAVA_ROOT");
-    gather_env("JDK_HOME");
-    gather_env("JRE_HOME");
+/// Detects available Java runtimes within the specified path between a minimum and a maximum depth, and appends them to the given vector.
+///
+/// Directories shallower than `min_depth` are walked through but not checked, e.g. with `min_depth` of 1,
+/// only the subdirectories of `/usr/lib/jvm` are checked, not itself.
+///
+/// # Parameters
+///
+/// * `runtimes`: Vector to contain detected Java runtimes.
+/// * `path`: The path to search for Java runtimes.
+/// * `min_depth`: Minimum depth to check for Java runtimes (see [`WalkDir::min_depth`]).
+/// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+///
+/// # Returns
+///
+/// The number of new Java runtimes added to the vector.
+pub fn gather_java_with_depth(
+    runtimes: &mut Vec<JavaRuntime>,
+    path: &Path,
+    min_depth: usize,
+    max_depth: usize,
+) -> usize {
+    let begin_count = runtimes.len();
+    runtimes.extend(
+        DetectorConfig::new()
+            .min_depth(min_depth)
+            .max_depth(max_depth)
+            .walk(path),
+    );
+    runtimes.len() - begin_count
+}
 
-    if let Ok(env_path) = std::env::var("PATH") {
-        let paths = env_path
-            .split(r":|;")
-            .map(Path::new)
-            .collect::<Vec<&Path>>();
-        gather_java_in_paths(&mut runtimes, &paths, 1);
+/// Detects available Java runtimes within the specified path like [`gather_java`], and collects the errors while walking.
+///
+/// Errors like lacking permission to read a directory are returned instead of ignored, so they can be reported.
+///
+/// # Returns
+///
+/// The number of new Java runtimes added to the vector, and the errors while walking.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs, io};
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-collect-errors");
+/// # let bin = root.join("jdk-17/bin");
+/// # fs::create_dir_all(&bin).unwrap();
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// # fs::write(bin.join("java"), script).unwrap();
+/// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// # fs::create_dir_all(root.join("private")).unwrap();
+/// // root/jdk-17/bin/java
+/// // root/private, which can't be read
+/// fs::set_permissions(root.join("private"), fs::Permissions::from_mode(0o000)).unwrap();
+///
+/// let mut runtimes = vec![];
+/// let (count, errors) = detector::gather_java_collect_errors(&mut runtimes, &root, 2);
+/// assert_eq!(count, 1);
+/// // Root can read it anyway
+/// if fs::read_dir(root.join("private")).is_err() {
+///     assert_eq!(errors.len(), 1);
+///     assert_eq!(errors[0].kind(), io::ErrorKind::PermissionDenied);
+/// }
+/// # fs::set_permissions(root.join("private"), fs::Permissions::from_mode(0o755)).unwrap();
+///
+/// let (count, errors) = detector::gather_java_collect_errors(&mut runtimes, &root.join("missing"), 2);
+/// assert_eq!(count, 0);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].kind(), io::ErrorKind::NotFound);
+/// # }
+/// ```
+pub fn gather_java_collect_errors(
+    runtimes: &mut Vec<JavaRuntime>,
+    path: &Path,
+    max_depth: usize,
+) -> (usize, Vec<std::io::Error>) {
+    let config = DetectorConfig::new().max_depth(max_depth);
+    let begin_count = runtimes.len();
+    let mut errors = vec![];
+    for entry in config.entries_with_errors(path) {
+        match entry {
+            Ok(entry) => runtimes.extend(config.detect_bin_dir(entry.path())),
+            Err(err) => errors.push(err.into()),
+        }
     }
-    runtimes
+    (runtimes.len() - begin_count, errors)
 }
 
-/// Detects available Java runtimes within multiple paths up to a maximum depth.
+/// Detects available Java runtimes within the specified path like [`gather_java`], and reports each entry examined.
+///
+/// It's useful for showing progress like `Scanning /usr/lib/jvm/...` while detecting.
 ///
 /// # Parameters
 ///
-/// * `paths`: The paths to search for Java runtimes.
+/// * `runtimes`: Vector to contain detected Java runtimes.
+/// * `path`: The path to search for Java runtimes.
 /// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+/// * `on_entry`: Called with the path of each directory entry before it's examined.
 ///
 /// # Returns
 ///
-/// A vector containing all detected Java runtimes.
-pub fn detect_java_in_paths<'a>(paths: &[&Path], max_depth: usize) -> Vec<JavaRuntime> {
-    let mut runtimes: Vec<JavaRuntime> = vec![];
-    for &path in paths {
-        gather_java(&mut runtimes, path, max_depth);
+/// The number of new Java runtimes added to the vector.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-progress");
+/// # let bin = root.join("jdk-17/bin");
+/// # fs::create_dir_all(&bin).unwrap();
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// # fs::write(bin.join("java"), script).unwrap();
+/// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// // root/jdk-17/bin/java
+/// let mut runtimes = vec![];
+/// let mut examined = vec![];
+/// let count = detector::gather_java_with_progress(&mut runtimes, &root, 2, &mut |path| {
+///     examined.push(path.to_path_buf());
+/// });
+/// assert_eq!(count, 1);
+/// assert!(examined.contains(&root));
+/// assert!(examined.contains(&root.join("jdk-17/bin")));
+/// # }
+/// ```
+pub fn gather_java_with_progress(
+    runtimes: &mut Vec<JavaRuntime>,
+    path: &Path,
+    max_depth: usize,
+    on_entry: &mut dyn FnMut(&Path),
+) -> usize {
+    let config = DetectorConfig::new().max_depth(max_depth);
+    let begin_count = runtimes.len();
+    for entry in config.entries(path) {
+        on_entry(entry.path());
+        runtimes.extend(config.detect_bin_dir(entry.path()));
     }
-    runtimes
+    runtimes.len() - begin_count
 }
 
-/// Detects available Java runtimes within multiple paths up to a maximum depth and appends them to the given vector.
+/// Detects available Java runtimes within the specified path like [`gather_java`], and stops early once `cancel` is set.
+///
+/// Cancellation is cooperative: the flag is checked between directory entries, so a running `java -version`
+/// is waited for, and the runtime it reports is still added.
 ///
 /// # Parameters
 ///
 /// * `runtimes`: Vector to contain detected Java runtimes.
-/// * `paths`: The paths to search for Java runtimes.
+/// * `path`: The path to search for Java runtimes.
 /// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+/// * `cancel`: Set it to `true`, e.g. from another thread, to stop the walk.
 ///
 /// # Returns
 ///
-/// The number of new Java runtimes added to the vector.
-pub fn gather_java_in_paths<'a>(
+/// The number of new Java runtimes added to the vector before cancelled.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-cancellable");
+/// let (started, resume) = (root.join("started"), root.join("resume"));
+/// # let _ = fs::remove_file(&started);
+/// # let _ = fs::remove_file(&resume);
+/// // root/jdk-11/bin/java, root/jdk-17/bin/java
+/// // Each java waits for `resume` after creating `started`
+/// # for home in ["jdk-11", "jdk-17"] {
+/// #     let bin = root.join(home).join("bin");
+/// #     fs::create_dir_all(&bin).unwrap();
+/// #     let script = format!(
+/// #         "#!/bin/sh\ntouch '{}'\nwhile [ ! -f '{}' ]; do sleep 0.01; done\necho 'openjdk version \"17.0.4.1\"' >&2\n",
+/// #         started.display(),
+/// #         resume.display()
+/// #     );
+/// #     fs::write(bin.join("java"), script).unwrap();
+/// #     fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// # }
+/// let cancel = AtomicBool::new(false);
+/// let mut runtimes = vec![];
+/// thread::scope(|scope| {
+///     // Cancel once the first java is executed
+///     scope.spawn(|| {
+///         while !started.exists() {
+///             thread::sleep(Duration::from_millis(10));
+///         }
+///         cancel.store(true, Ordering::Relaxed);
+///         fs::write(&resume, "").unwrap();
+///     });
+///     let count = detector::gather_java_cancellable(&mut runtimes, &root, 2, &cancel);
+///     assert_eq!(count, 1);
+/// });
+///
+/// // Already cancelled
+/// assert_eq!(detector::gather_java_cancellable(&mut runtimes, &root, 2, &cancel), 0);
+/// # }
+/// ```
+pub fn gather_java_cancellable(
     runtimes: &mut Vec<JavaRuntime>,
-    paths: &[&Path],
+    path: &Path,
     max_depth: usize,
+    cancel: &AtomicBool,
 ) -> usize {
-    paths
+    let config = DetectorConfig::new().max_depth(max_depth);
+    let begin_count = runtimes.len();
+    for entry in config.entries(path) {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        runtimes.extend(config.detect_bin_dir(entry.path()));
+    }
+    runtimes.len() - begin_count
+}
+
+/// Lazily walks the specified path up to a maximum depth, and yields the detected Java runtimes.
+///
+/// Unlike [`detect_java`], the runtimes are yielded as they are found, so they can be shown before the walk finishes,
+/// or the walk can be stopped early with adapters like [`Iterator::take`].
+///
+/// # Parameters
+///
+/// * `path`: The path to search for Java runtimes.
+/// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-iter-java");
+/// # for home in ["jdk-11", "jdk-17"] {
+/// #     let bin = root.join(home).join("bin");
+/// #     fs::create_dir_all(&bin).unwrap();
+/// #     let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// #     fs::write(bin.join("java"), script).unwrap();
+/// #     fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// # }
+/// // root/jdk-11/bin/java, root/jdk-17/bin/java
+/// let mut runtimes = detector::iter_java(&root, 2);
+/// let first = runtimes.next().unwrap();
+/// assert!(first.get_executable().starts_with(&root));
+///
+/// // The rest of the walk continues from where it stopped
+/// assert_eq!(runtimes.count(), 1);
+///
+/// for runtime in detector::iter_java(&root, 2) {
+///     println!("Found {}", runtime);
+/// }
+/// # }
+/// ```
+pub fn iter_java(path: &Path, max_depth: usize) -> impl Iterator<Item = JavaRuntime> {
+    DetectorConfig::new().max_depth(max_depth).walk(path)
+}
+
+/// Options for detecting Java runtimes within a path.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector::DetectorConfig;
+///
+/// let runtimes = DetectorConfig::new()
+///     .max_depth(2)
+///     .release_file_fallback(true)
+///     .detect("/usr".as_ref());
+/// println!("Detected Java runtimes: {:?}", runtimes);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DetectorConfig {
+    max_depth: usize,
+    min_depth: usize,
+    max_visited: usize,
+    limit: usize,
+    follow_links: bool,
+    exclude_dirs: Vec<OsString>,
+    release_file_fallback: bool,
+    include_hidden: bool,
+    executable_names: Vec<OsString>,
+    command_prober: CommandProber,
+    prober: Option<Arc<dyn VersionProber>>,
+}
+
+impl Default for DetectorConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            min_depth: 0,
+            max_visited: usize::MAX,
+            limit: usize::MAX,
+            follow_links: false,
+            exclude_dirs: vec![],
+            release_file_fallback: false,
+            include_hidden: true,
+            executable_names: vec![],
+            command_prober: CommandProber::new(),
+            prober: None,
+        }
+    }
+}
+
+impl DetectorConfig {
+    /// Create a config with default options
+    ///
+    /// * `max_depth`: [`DEFAULT_MAX_DEPTH`]
+    /// * `min_depth`: `0`
+    /// * `max_visited`: unlimited
+    /// * `limit`: unlimited
+    /// * `follow_links`: `false`
+    /// * `exclude_dir`: none
+    /// * `release_file_fallback`: `false`
+    /// * `include_hidden`: `true`
+    /// * `executable_name`: only `java(.exe)`
+    /// * `prober`: [`CommandProber`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+    ///
+    /// A warning is logged if it's above [`MAX_DEPTH_WARNING_THRESHOLD`].
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        if max_depth > MAX_DEPTH_WARNING_THRESHOLD {
+            log::warn!(
+                "max_depth {} is above {}, detecting java runtimes may be very slow",
+                max_depth,
+                MAX_DEPTH_WARNING_THRESHOLD
+            );
+        }
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set the minimum depth of directories to check for a `java` executable file (see [`WalkDir::min_depth`]).
+    pub fn min_depth(mut self, min_depth: usize) -> Self {
+        self.min_depth = min_depth;
+        self
+    }
+
+    /// Set the maximum number of runtimes to detect within a path.
+    ///
+    /// The walk stops as soon as this many runtimes are found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::detector::DetectorConfig;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-limit");
+    /// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+    /// // root/jdk-17/bin/java, root/jdk-21/bin/java
+    /// for home in ["jdk-17", "jdk-21"] {
+    ///     let bin = root.join(home).join("bin");
+    /// #   fs::create_dir_all(&bin).unwrap();
+    /// #   fs::write(bin.join("java"), script).unwrap();
+    /// #   fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// }
+    ///
+    /// assert_eq!(DetectorConfig::new().detect(&root).len(), 2);
+    /// assert_eq!(DetectorConfig::new().limit(1).detect(&root).len(), 1);
+    ///
+    /// let report = DetectorConfig::new().limit(1).report(&[root.as_ref()]);
+    /// assert_eq!(report.get_runtimes().len(), 1);
+    /// # }
+    /// ```
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set whether to follow symbolic links while walking a path (see [`WalkDir::follow_links`]).
+    ///
+    /// Some installations are only exposed through symbolic links, like `/usr/lib/jvm/default`.
+    /// Links pointing back to their ancestors are detected by [`WalkDir`] and skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::detector::DetectorConfig;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-follow-links");
+    /// # let bin = root.join("real/jdk-17/bin");
+    /// # fs::create_dir_all(&bin).unwrap();
+    /// # fs::create_dir_all(root.join("jvm")).unwrap();
+    /// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+    /// # fs::write(bin.join("java"), script).unwrap();
+    /// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// # let _ = fs::remove_file(root.join("jvm/default"));
+    /// // root/jvm/default -> root/real/jdk-17
+    /// std::os::unix::fs::symlink(root.join("real/jdk-17"), root.join("jvm/default")).unwrap();
+    ///
+    /// let jvm = root.join("jvm");
+    /// assert!(DetectorConfig::new().detect(&jvm).is_empty());
+    ///
+    /// let runtimes = DetectorConfig::new().follow_links(true).detect(&jvm);
+    /// assert_eq!(runtimes.len(), 1);
+    /// assert_eq!(runtimes[0].get_executable(), jvm.join("default/bin/java"));
+    ///
+    /// // root/jvm/loop -> root/jvm
+    /// # let _ = fs::remove_file(root.join("jvm/loop"));
+    /// std::os::unix::fs::symlink(&jvm, jvm.join("loop")).unwrap();
+    /// let runtimes = DetectorConfig::new().max_depth(4).follow_links(true).detect(&jvm);
+    /// assert_eq!(runtimes.len(), 1);
+    /// # }
+    /// ```
+    pub fn follow_links(mut self, enabled: bool) -> Self {
+        self.follow_links = enabled;
+        self
+    }
+
+    /// Skip the directories of the given name while walking a path, like `node_modules` or `.git`
+    ///
+    /// Their subtrees are not walked at all. The given path itself is always walked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::detector::DetectorConfig;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-exclude-dir");
+    /// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+    /// // root/jdk-17/bin/java, root/node_modules/jdk-21/bin/java
+    /// for home in ["jdk-17", "node_modules/jdk-21"] {
+    ///     let bin = root.join(home).join("bin");
+    /// #   fs::create_dir_all(&bin).unwrap();
+    /// #   fs::write(bin.join("java"), script).unwrap();
+    /// #   fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// }
+    ///
+    /// let config = DetectorConfig::new().max_depth(3);
+    /// assert_eq!(config.clone().detect(&root).len(), 2);
+    ///
+    /// let runtimes = config.exclude_dir("node_modules").detect(&root);
+    /// assert_eq!(runtimes.len(), 1);
+    /// assert_eq!(runtimes[0].get_executable(), root.join("jdk-17/bin/java"));
+    ///
+    /// // The given path itself is walked even if it has an excluded name
+    /// let modules = root.join("node_modules");
+    /// let runtimes = DetectorConfig::new().max_depth(2).exclude_dir("node_modules").detect(&modules);
+    /// assert_eq!(runtimes.len(), 1);
+    /// # }
+    /// ```
+    pub fn exclude_dir(mut self, name: impl Into<OsString>) -> Self {
+        self.exclude_dirs.push(name.into());
+        self
+    }
+
+    /// Set the maximum number of entries to visit while walking a path.
+    ///
+    /// The walk stops after visiting this many entries, and the runtimes detected so far are returned.
+    /// It protects against broken or adversarial filesystems with huge trees.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::detector::DetectorConfig;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-max-visited");
+    /// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+    /// // 20 java homes in `root`
+    /// for i in 0..20 {
+    ///     let bin = root.join(format!("jdk-{}", i)).join("bin");
+    /// #   fs::create_dir_all(&bin).unwrap();
+    /// #   fs::write(bin.join("java"), script).unwrap();
+    /// #   fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// }
+    ///
+    /// assert_eq!(DetectorConfig::new().max_depth(2).detect(&root).len(), 20);
+    ///
+    /// // Visits `root`, then `jdk-*` and `jdk-*/bin` of two homes, whichever come first
+    /// let runtimes = DetectorConfig::new().max_depth(2).max_visited(5).detect(&root);
+    /// assert_eq!(runtimes.len(), 2);
+    /// # }
+    /// ```
+    pub fn max_visited(mut self, max_visited: usize) -> Self {
+        self.max_visited = max_visited;
+        self
+    }
+
+    /// Set whether to walk into hidden directories, whose names start with `.`
+    ///
+    /// Some tools install runtimes in hidden directories, like `~/.jdks` of IntelliJ IDEA and `~/.sdkman`.
+    /// The given path itself is always walked, even if it's hidden.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::detector::DetectorConfig;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-include-hidden");
+    /// # let bin = root.join(".jdks/jdk-17/bin");
+    /// # fs::create_dir_all(&bin).unwrap();
+    /// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+    /// # fs::write(bin.join("java"), script).unwrap();
+    /// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// // root/.jdks/jdk-17/bin/java
+    /// let config = DetectorConfig::new().max_depth(3);
+    /// assert_eq!(config.clone().include_hidden(true).detect(&root).len(), 1);
+    /// assert!(config.clone().include_hidden(false).detect(&root).is_empty());
+    ///
+    /// assert_eq!(config.include_hidden(false).detect(&root.join(".jdks")).len(), 1);
+    /// # }
+    /// ```
+    pub fn include_hidden(mut self, enabled: bool) -> Self {
+        self.include_hidden = enabled;
+        self
+    }
+
+    /// Set whether to record runtimes that can not be executed by the current user.
+    ///
+    /// When enabled and executing java is denied, the version is read from the `release` file in java home instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::detector::DetectorConfig;
+    ///
+    /// # let home = env::temp_dir().join("java-runtimes-doctest-release-fallback/jdk-17");
+    /// # fs::create_dir_all(home.join("bin")).unwrap();
+    /// # fs::write(home.join("bin/java"), "#!/bin/sh\n").unwrap();
+    /// # fs::write(home.join("release"), "JAVA_VERSION=\"17.0.4.1\"\n").unwrap();
+    /// // `bin/java` is not executable, but `release` file is readable
+    /// fs::set_permissions(home.join("bin/java"), fs::Permissions::from_mode(0o644)).unwrap();
+    ///
+    /// assert!(DetectorConfig::new().detect(&home).is_empty());
+    ///
+    /// let runtimes = DetectorConfig::new().release_file_fallback(true).detect(&home);
+    /// assert_eq!(runtimes.len(), 1);
+    /// assert_eq!(runtimes[0].get_version_string(), "17.0.4.1");
+    /// # }
+    /// ```
+    pub fn release_file_fallback(mut self, enabled: bool) -> Self {
+        self.release_file_fallback = enabled;
+        self
+    }
+
+    /// Set the prober used to get the version output of the java executable files found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::detector::DetectorConfig;
+    /// use java_runtimes::error::Error;
+    /// use java_runtimes::prober::VersionProber;
+    /// use std::path::Path;
+    /// use std::{env, fs};
+    ///
+    /// /// Reports the version from the name of java home, like `jdk-17.0.4.1`
+    /// #[derive(Debug)]
+    /// struct MockProber;
+    ///
+    /// impl VersionProber for MockProber {
+    ///     fn probe(&self, executable: &Path) -> Result<String, Error> {
+    ///         let home = executable.parent().unwrap().parent().unwrap();
+    ///         let version = home.file_name().unwrap().to_str().unwrap().trim_start_matches("jdk-");
+    ///         Ok(format!("openjdk version \"{}\"", version))
+    ///     }
+    /// }
+    ///
+    /// let root = env::temp_dir().join("java-runtimes-doctest-prober");
+    /// for home in ["jdk-11.0.2", "jdk-17.0.4.1"] {
+    ///     // Empty files are enough
+    ///     fs::create_dir_all(root.join(home).join("bin")).unwrap();
+    ///     fs::write(root.join(home).join("bin/java"), "").unwrap();
+    /// }
+    ///
+    /// let mut runtimes = DetectorConfig::new().prober(MockProber).detect(&root);
+    /// runtimes.sort_by(|a, b| a.get_executable().cmp(b.get_executable()));
+    /// assert_eq!(runtimes.len(), 2);
+    /// assert_eq!(runtimes[0].get_version_string(), "11.0.2");
+    /// assert_eq!(runtimes[1].get_version_string(), "17.0.4.1");
+    /// ```
+    pub fn prober(mut self, prober: impl VersionProber + 'static) -> Self {
+        self.prober = Some(Arc::new(prober));
+        self
+    }
+
+    /// Also look for java executable files of the given name besides `java(.exe)`, like `javaw.exe`
+    ///
+    /// Some GUI launchers on Windows prefer `javaw.exe`, and some wrappers use their own names.
+    /// If the `java(.exe)` and the other one are both in a directory, `java(.exe)` is used.
+    ///
+    /// Runtimes found as `javaw(.exe)` can be updated with [`JavaRuntime::update`] later.
+    /// For other names, use [`JavaRuntime::update_with_prober`] with [`CommandProber::executable_name`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::detector::DetectorConfig;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-executable-name");
+    /// # let bin = root.join("jre-8/bin");
+    /// # fs::create_dir_all(&bin).unwrap();
+    /// # let script = "#!/bin/sh\necho 'java version \"1.8.0_333\"' >&2\n";
+    /// # fs::write(bin.join("javaw"), script).unwrap();
+    /// # fs::set_permissions(bin.join("javaw"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// // root/jre-8/bin/javaw
+    /// assert!(DetectorConfig::new().detect(&root).is_empty());
+    ///
+    /// let mut runtimes = DetectorConfig::new().executable_name("javaw").detect(&root);
+    /// assert_eq!(runtimes.len(), 1);
+    /// assert_eq!(runtimes[0].get_executable(), bin.join("javaw"));
+    /// assert_eq!(runtimes[0].get_version_string(), "1.8.0_333");
+    ///
+    /// runtimes[0].update().unwrap();
+    /// assert_eq!(runtimes[0].get_version_string(), "1.8.0_333");
+    /// # }
+    /// ```
+    pub fn executable_name(mut self, name: impl Into<OsString>) -> Self {
+        let name = name.into();
+        self.command_prober = self.command_prober.executable_name(name.clone());
+        self.executable_names.push(name);
+        self
+    }
+
+    /// Detects available Java runtimes within the specified path.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing all detected Java runtimes.
+    pub fn detect(&self, path: &Path) -> Vec<JavaRuntime> {
+        let mut runtimes: Vec<JavaRuntime> = vec![];
+        self.gather(&mut runtimes, path);
+        runtimes
+    }
+
+    /// Detects available Java runtimes within the specified path and appends them to the given vector.
+    ///
+    /// # Returns
+    ///
+    /// The number of new Java runtimes added to the vector.
+    pub fn gather(&self, runtimes: &mut Vec<JavaRuntime>, path: &Path) -> usize {
+        if path.is_file() {
+            if let Some(runtime) = self.detect_bin_dir(path) {
+                runtimes.push(runtime);
+                return 1;
+            }
+        }
+
+        let begin_count = runtimes.len();
+        runtimes.extend(self.walk(path));
+        runtimes.len() - begin_count
+    }
+
+    /// Detects available Java runtimes within multiple paths, and records statistics of the scan.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::detector::DetectorConfig;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-report");
+    /// # let bin = root.join("jdk-17/bin");
+    /// # fs::create_dir_all(&bin).unwrap();
+    /// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+    /// # fs::write(bin.join("java"), script).unwrap();
+    /// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// // root/jdk-17/bin/java
+    /// let report = DetectorConfig::new().max_depth(2).report(&[root.as_ref()]);
+    /// assert_eq!(report.get_runtimes().len(), 1);
+    /// assert_eq!(report.get_dirs_scanned(), 3);
+    /// assert!(report.get_scan_duration().as_nanos() > 0);
+    /// # }
+    /// ```
+    pub fn report(&self, paths: &[&Path]) -> DetectionReport {
+        let begin = Instant::now();
+        let mut runtimes: Vec<JavaRuntime> = vec![];
+        let mut dirs_scanned = 0;
+        for &path in paths {
+            let begin_count = runtimes.len();
+            for entry in self.entries(path) {
+                if runtimes.len() - begin_count >= self.limit {
+                    break;
+                }
+                if entry.file_type().is_dir() {
+                    dirs_scanned += 1;
+                }
+                if let Some(runtime) = self.detect_bin_dir(entry.path()) {
+                    runtimes.push(runtime);
+                }
+            }
+        }
+        DetectionReport::with_stats(runtimes, begin.elapsed(), dirs_scanned)
+    }
+
+    /// Lazily walks the given path and yields the detected Java runtimes
+    fn walk(&self, path: &Path) -> impl Iterator<Item = JavaRuntime> {
+        let config = self.clone();
+        self.entries(path)
+            .filter_map(move |entry| config.detect_bin_dir(entry.path()))
+            .take(self.limit)
+    }
+
+    /// Detects Java runtimes like [`DetectorConfig::gather`], and collects the errors instead of ignoring them
+    fn gather_with_errors(
+        &self,
+        runtimes: &mut Vec<JavaRuntime>,
+        errors: &mut Vec<Error>,
+        path: &Path,
+    ) {
+        if path.is_file() {
+            match self.detect_bin_dir_checked(path) {
+                Some(Ok(runtime)) => return runtimes.push(runtime),
+                Some(Err(err)) => return errors.push(err),
+                None => {}
+            }
+        }
+        for entry in self.entries_with_errors(path) {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    errors.push(ErrorKind::ReadingDirFailed(err.into()).into());
+                    continue;
+                }
+            };
+            match self.detect_bin_dir_checked(entry.path()) {
+                Some(Ok(runtime)) => runtimes.push(runtime),
+                Some(Err(err)) => errors.push(err),
+                None => {}
+            }
+        }
+    }
+
+    /// Lazily walks the given path and yields the entries to check
+    fn entries(&self, path: &Path) -> impl Iterator<Item = DirEntry> {
+        self.entries_with_errors(path).filter_map(Result::ok)
+    }
+
+    /// Lazily walks the given path and yields the entries to check, or the errors while walking
+    fn entries_with_errors(&self, path: &Path) -> impl Iterator<Item = walkdir::Result<DirEntry>> {
+        let include_hidden = self.include_hidden;
+        let exclude_dirs = self.exclude_dirs.clone();
+        WalkDir::new(path)
+            .min_depth(self.min_depth)
+            .max_depth(self.max_depth)
+            .follow_links(self.follow_links)
+            .into_iter()
+            .filter_entry(move |entry| {
+                if entry.depth() == 0 {
+                    return true;
+                }
+                let name = entry.file_name();
+                (include_hidden || !name.to_string_lossy().starts_with('.'))
+                    && !(entry.file_type().is_dir() && exclude_dirs.iter().any(|dir| dir == name))
+            })
+            .take(self.max_visited)
+    }
+
+    /// Attempts to detect a Java runtime from the given directory path, see [`detect_java_bin_dir`]
+    fn detect_bin_dir(&self, bin_dir: &Path) -> Option<JavaRuntime> {
+        self.detect_bin_dir_checked(bin_dir)?.ok()
+    }
+
+    /// Attempts to detect a Java runtime from the given directory path
+    ///
+    /// Returns `None` if there is no java executable file in it.
+    fn detect_bin_dir_checked(&self, bin_dir: &Path) -> Option<Result<JavaRuntime, Error>> {
+        let executable = std::iter::once(JavaRuntime::get_java_executable_name())
+            .chain(self.executable_names.iter().cloned())
+            .map(|name| bin_dir.join(name))
+            .find(|executable| executable.is_file())?;
+        let prober: &dyn VersionProber = match &self.prober {
+            Some(prober) => prober.as_ref(),
+            None => &self.command_prober,
+        };
+        match JavaRuntime::from_executable_with_prober(&executable, prober) {
+            Err(err) if self.release_file_fallback && err.is_permission_denied() => {
+                Some(JavaRuntime::from_release(&executable))
+            }
+            result => Some(result),
+        }
+    }
+}
+
+/// Detects available Java runtimes from environment variables.
+///
+/// It searches java runtime in paths below:
+///
+/// * `JAVA_HOME`
+/// * `JAVA_ROOT`
+/// * `JDK_HOME`
+/// * `JRE_HOME`
+/// * Each directory in `PATH`, split by the separator of current platform (`:` on unix, `;` on windows)
+///
+/// A runtime found through multiple variables is reported only once.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_java_in_environments();
+/// println!("Detected Java runtimes: {:?}", runtimes);
+/// ```
+pub fn detect_java_in_environments() -> Vec<JavaRuntime> {
+    let mut runtimes: Vec<JavaRuntime> = vec![];
+    for path in environment_paths() {
+        gather_java(&mut runtimes, &path, 1);
+    }
+    dedup_by_canonical_path(&mut runtimes);
+    runtimes
+}
+
+/// Environment variables pointing to a java home directory, see [`detect_java_in_environments`]
+const JAVA_HOME_VARS: [&str; 4] = ["JAVA_HOME", "JAVA_ROOT", "JDK_HOME", "JRE_HOME"];
+
+/// Collects the paths to search from environment variables, see [`detect_java_in_environments`]
+fn environment_paths() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = JAVA_HOME_VARS
         .iter()
-        .map(|&path| gather_java(runtimes, path, max_depth))
-        .sum::<usize>()
+        .filter_map(|var_name| std::env::var(var_name).ok())
+        .map(PathBuf::from)
+        .collect();
+
+    if let Some(env_path) = std::env::var_os("PATH") {
+        paths.extend(std::env::split_paths(&env_path));
+    }
+    paths
 }
 
-/// Attempts to detect a Java runtime from the given path.
+/// Finds the first Java runtime matching the predicate from environment variables.
+///
+/// Paths are searched in the same order as [`detect_java_in_environments`], but lazily:
+/// the search stops as soon as a matching runtime is found.
 ///
 /// # Returns
 ///
-/// * `Some(JavaRuntime)` if the given path points to an available Java executable file.
-/// * `None` if the given path is not an available Java executable file.
-pub fn detect_java_exe(path: &Path) -> Option<JavaRuntime> {
-    JavaRuntime::from_executable(path).map_or(None, |r| Some(r))
+/// * `Some(JavaRuntime)` if a runtime matching the predicate is found.
+/// * `None` if no such runtime is found.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtime = detector::find_where(|runtime| runtime.get_major_version() >= 17);
+/// println!("First Java 17+ runtime: {:?}", runtime);
+/// ```
+pub fn find_where(predicate: impl Fn(&JavaRuntime) -> bool) -> Option<JavaRuntime> {
+    environment_paths()
+        .iter()
+        .flat_map(|path| iter_java(path, 1))
+        .find(|runtime| predicate(runtime))
 }
 
-/// Attempts to detect a Java runtime from the given directory path.
+/// Detects Java runtimes from a string of java homes, delimited like `PATH`.
+///
+/// The string is split with [`std::env::split_paths`], so the delimiter is `;` on Windows and `:` elsewhere.
+/// Each part is resolved as a java home, see [`detect_java_home_dir`].
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_from_delimited("/usr/lib/jvm/jdk-17:/opt/jdk-21");
+/// println!("Detected Java runtimes: {:?}", runtimes);
+/// ```
+pub fn detect_from_delimited(homes: &str) -> Vec<JavaRuntime> {
+    std::env::split_paths(homes)
+        .filter_map(|home| detect_java_home_dir(&home))
+        .collect()
+}
+
+/// Enum [`Diagnostic`] describes a problem of the java setup in environment variables, see [`diagnose_environment`].
+///
+/// It's displayed as a human-readable message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic {
+    /// `JAVA_HOME` is set, but it's not a valid java home
+    InvalidJavaHome(PathBuf),
+    /// `JAVA_HOME` and the first `java` on `PATH` are of different major versions
+    JavaHomeConflictsWithPath {
+        /// The runtime in `JAVA_HOME`
+        java_home: Box<JavaRuntime>,
+        /// The first runtime found on `PATH`
+        path: Box<JavaRuntime>,
+    },
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnostic::InvalidJavaHome(java_home) => {
+                write!(
+                    f,
+                    "JAVA_HOME is not a valid java home: {}",
+                    java_home.display()
+                )
+            }
+            Diagnostic::JavaHomeConflictsWithPath { java_home, path } => {
+                write!(
+                    f,
+                    "JAVA_HOME is Java {} but `java` is Java {} ({})",
+                    java_home.get_major_version(),
+                    path.get_major_version(),
+                    path.get_executable().display()
+                )
+            }
+        }
+    }
+}
+
+/// Diagnoses the java setup in environment variables.
+///
+/// It checks for these problems:
+///
+/// * `JAVA_HOME` is set but not a valid java home.
+/// * `JAVA_HOME` and the first `java` on `PATH` are of different major versions,
+///   so tools using `JAVA_HOME` and the `java` command behave differently.
 ///
 /// # Returns
 ///
-/// * `Some(JavaRuntime)` if the given path is a directory containing the Java executable file.
-/// * `None` if the given path is not a directory containing the Java executable file.
-pub fn detect_java_bin_dir(bin_dir: &Path) -> Option<JavaRuntime> {
-    detect_java_exe(&bin_dir.join(JavaRuntime::get_java_executable_name()))
+/// The problems found, empty if nothing is wrong.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// for diagnostic in detector::diagnose_environment() {
+///     println!("warning: {}", diagnostic);
+/// }
+/// ```
+pub fn diagnose_environment() -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let Some(java_home_path) = std::env::var_os("JAVA_HOME").map(PathBuf::from) else {
+        return diagnostics;
+    };
+    let Some(java_home) = detect_java_home_dir(&java_home_path) else {
+        diagnostics.push(Diagnostic::InvalidJavaHome(java_home_path));
+        return diagnostics;
+    };
+
+    let path_java = std::env::var_os("PATH")
+        .and_then(|paths| std::env::split_paths(&paths).find_map(|dir| detect_java_bin_dir(&dir)));
+    if let Some(path) = path_java {
+        if path.get_major_version() != java_home.get_major_version() {
+            diagnostics.push(Diagnostic::JavaHomeConflictsWithPath {
+                java_home: Box::new(java_home),
+                path: Box::new(path),
+            });
+        }
+    }
+    diagnostics
 }
 
-/// Attempts to detect a Java runtime from the given Java home directory path.
+/// Detects the Java runtime configured for Gradle builds of the given project.
+///
+/// It reads `org.gradle.java.home` from these `gradle.properties` files, the first one found wins:
+///
+/// * The user-level one in `$GRADLE_USER_HOME`, or `~/.gradle` if it's not set
+/// * The project-level one in the given directory
+///
+/// This is the same precedence as Gradle's.
 ///
 /// # Returns
 ///
-/// * `Some(JavaRuntime)` if the given path is a directory containing the `bin` subdirectory with the Java executable file.
-/// * `None` if the given path is not a directory containing the `bin` subdirectory with the Java executable file.
-pub fn detect_java_home_dir(java_home: &Path) -> Option<JavaRuntime> {
-    detect_java_bin_dir(&java_home.join("bin"))
+/// * `Some(JavaRuntime)` if `org.gradle.java.home` is found and it's a valid java home.
+/// * `None` otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// if let Some(runtime) = detector::detect_java_from_gradle_properties(".".as_ref()) {
+///     println!("Gradle builds with {:?}", runtime.get_executable());
+/// }
+/// ```
+pub fn detect_java_from_gradle_properties(dir: &Path) -> Option<JavaRuntime> {
+    detect_java_home_dir(&gradle_java_home(dir)?)
+}
+
+/// Read the java home configured for Gradle builds of the given project, see [`detect_java_from_gradle_properties`]
+fn gradle_java_home(dir: &Path) -> Option<PathBuf> {
+    let user_home = std::env::var_os("GRADLE_USER_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".gradle")))
+        .or_else(|| std::env::var_os("USERPROFILE").map(|home| Path::new(&home).join(".gradle")));
+
+    user_home
+        .into_iter()
+        .chain([dir.to_path_buf()])
+        .find_map(|dir| {
+            read_properties_entry(&dir.join("gradle.properties"), "org.gradle.java.home")
+        })
+        .map(PathBuf::from)
+}
+
+/// Detects Java runtimes installed by [SDKMAN!](https://sdkman.io).
+///
+/// It searches the java homes in `$SDKMAN_DIR/candidates/java`, or `~/.sdkman/candidates/java` if it's not set.
+/// The `current` symlink to the default version is skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_java_in_sdkman();
+/// println!("Installed by SDKMAN!: {:?}", runtimes);
+/// ```
+pub fn detect_java_in_sdkman() -> Vec<JavaRuntime> {
+    detect_java_in_homes(&sdkman_java_homes())
+}
+
+/// Collects the java homes installed by SDKMAN!, see [`detect_java_in_sdkman`]
+fn sdkman_java_homes() -> Vec<PathBuf> {
+    let Some(sdkman_dir) = tool_dir("SDKMAN_DIR", ".sdkman") else {
+        return vec![];
+    };
+    read_dir_paths(&sdkman_dir.join("candidates/java"))
+        .into_iter()
+        .filter(|home| home.file_name() != Some("current".as_ref()))
+        .collect()
+}
+
+/// Detects Java runtimes installed by [asdf](https://asdf-vm.com).
+///
+/// It searches the java homes in `$ASDF_DATA_DIR/installs/java`, or `~/.asdf/installs/java` if it's not set.
+/// The shims in `shims` are wrappers selecting one of the installs, so they are not searched.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_java_in_asdf();
+/// println!("Installed by asdf: {:?}", runtimes);
+/// ```
+pub fn detect_java_in_asdf() -> Vec<JavaRuntime> {
+    detect_java_in_homes(&asdf_java_homes())
+}
+
+/// Collects the java homes installed by asdf, see [`detect_java_in_asdf`]
+fn asdf_java_homes() -> Vec<PathBuf> {
+    let Some(asdf_dir) = tool_dir("ASDF_DATA_DIR", ".asdf") else {
+        return vec![];
+    };
+    read_dir_paths(&asdf_dir.join("installs/java"))
+}
+
+/// Get the data directory of a tool from the given environment variable, or `~/<default_name>` if it's not set
+fn tool_dir(var_name: &str, default_name: &str) -> Option<PathBuf> {
+    std::env::var_os(var_name).map(PathBuf::from).or_else(|| {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| Path::new(&home).join(default_name))
+    })
+}
+
+/// Read the value of the given key from a java `.properties` file
+///
+/// Lines starting with `#` or `!` are comments, and backslash escapes like `C\:\\Java` are resolved.
+fn read_properties_entry(file: &Path, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(file).ok()?;
+    content.lines().find_map(|line| {
+        let line = line.trim_start();
+        if line.starts_with(['#', '!']) {
+            return None;
+        }
+        let (entry_key, value) = line.split_once(['=', ':'])?;
+        if entry_key.trim() != key {
+            return None;
+        }
+        let mut unescaped = String::new();
+        let mut chars = value.trim().chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => unescaped.extend(chars.next()),
+                c => unescaped.push(c),
+            }
+        }
+        Some(unescaped)
+    })
+}
+
+/// Detects the Java runtime used by a running process, from the `JAVA_HOME` in its environment.
+///
+/// It reads the environment from `/proc/<pid>/environ`, see [`detect_java_from_environ_file`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+/// use std::io::{BufRead, BufReader};
+/// use std::process::{Command, Stdio};
+///
+/// # let home = env::temp_dir().join("java-runtimes-doctest-proc-env/jdk-17");
+/// # fs::create_dir_all(home.join("bin")).unwrap();
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// # fs::write(home.join("bin/java"), script).unwrap();
+/// # fs::set_permissions(home.join("bin/java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// let mut server = Command::new("sh")
+///     .args(["-c", "echo started; read line"])
+///     .env("JAVA_HOME", &home)
+///     .stdin(Stdio::piped())
+///     .stdout(Stdio::piped())
+///     .spawn()
+///     .unwrap();
+/// # let mut started = String::new();
+/// # BufReader::new(server.stdout.take().unwrap()).read_line(&mut started).unwrap();
+///
+/// let runtime = detector::detect_java_from_proc_env(server.id()).unwrap();
+/// assert_eq!(runtime.get_executable(), home.join("bin/java"));
+/// # server.kill().unwrap();
+/// # server.wait().unwrap();
+/// ```
+#[cfg(target_os = "linux")]
+pub fn detect_java_from_proc_env(pid: u32) -> Option<JavaRuntime> {
+    detect_java_from_environ_file(&PathBuf::from(format!("/proc/{}/environ", pid)))
+}
+
+/// Detects the Java runtime from the `JAVA_HOME` in an environment snapshot file.
+///
+/// The file consists of null-delimited `KEY=value` entries, like `/proc/<pid>/environ` on Linux.
+///
+/// # Returns
+///
+/// * `Some(JavaRuntime)` if `JAVA_HOME` is found in the file and it's a valid java home.
+/// * `None` otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-environ");
+/// # let home = root.join("jdk-17");
+/// # fs::create_dir_all(home.join("bin")).unwrap();
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// # fs::write(home.join("bin/java"), script).unwrap();
+/// # fs::set_permissions(home.join("bin/java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// let environ = format!("LANG=C\0JAVA_HOME={}\0PATH=/usr/bin\0", home.display());
+/// fs::write(root.join("environ"), environ).unwrap();
+///
+/// let runtime = detector::detect_java_from_environ_file(&root.join("environ")).unwrap();
+/// assert_eq!(runtime.get_version_string(), "17.0.4.1");
+///
+/// fs::write(root.join("environ"), "LANG=C\0PATH=/usr/bin\0").unwrap();
+/// assert!(detector::detect_java_from_environ_file(&root.join("environ")).is_none());
+/// # }
+/// ```
+pub fn detect_java_from_environ_file(environ_file: &Path) -> Option<JavaRuntime> {
+    let environ = std::fs::read(environ_file).ok()?;
+    let java_home = environ
+        .split(|&byte| byte == 0)
+        .find_map(|entry| entry.strip_prefix(b"JAVA_HOME="))?;
+    detect_java_home_dir(Path::new(&*String::from_utf8_lossy(java_home)))
+}
+
+/// Detects available Java runtimes within multiple paths up to a maximum depth.
+///
+/// # Parameters
+///
+/// * `paths`: The paths to search for Java runtimes.
+/// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+///
+/// # Returns
+///
+/// A vector containing all detected Java runtimes.
+pub fn detect_java_in_paths(paths: &[&Path], max_depth: usize) -> Vec<JavaRuntime> {
+    let mut runtimes: Vec<JavaRuntime> = vec![];
+    for &path in paths {
+        gather_java(&mut runtimes, path, max_depth);
+    }
+    runtimes
+}
+
+/// Detects available Java runtimes within multiple paths up to a maximum depth, with a result for each path.
+///
+/// Unlike [`detect_java_in_paths`], a path that can not be searched is reported instead of ignored.
+///
+/// # Parameters
+///
+/// * `paths`: The paths to search for Java runtimes.
+/// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+///
+/// # Returns
+///
+/// Each given path, and the Java runtimes detected in it, or one of the errors:
+///
+/// * [`ErrorKind::PathNotFound`] if the path does not exist
+/// * [`ErrorKind::ReadingDirFailed`] if the path is a directory that can not be read
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+/// use java_runtimes::error::ErrorKind;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-paths-checked");
+/// # let bin = root.join("jdk-17/bin");
+/// # fs::create_dir_all(&bin).unwrap();
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// # fs::write(bin.join("java"), script).unwrap();
+/// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// // root/jdk-17/bin/java
+/// let missing = root.join("missing");
+/// let results = detector::detect_java_in_paths_checked(&[&root, &missing], 2);
+/// assert_eq!(results.len(), 2);
+///
+/// assert_eq!(results[0].0, root);
+/// let runtimes = results[0].1.as_ref().unwrap();
+/// assert_eq!(runtimes.len(), 1);
+/// assert_eq!(runtimes[0].get_version_string(), "17.0.4.1");
+///
+/// assert_eq!(results[1].0, missing);
+/// let err = results[1].1.as_ref().unwrap_err();
+/// assert!(matches!(err.kind(), ErrorKind::PathNotFound(path) if *path == missing));
+/// # }
+/// ```
+pub fn detect_java_in_paths_checked(
+    paths: &[&Path],
+    max_depth: usize,
+) -> Vec<(PathBuf, Result<Vec<JavaRuntime>, Error>)> {
+    paths
+        .iter()
+        .map(|&path| {
+            let result = if !path.exists() {
+                Err(ErrorKind::PathNotFound(path.to_path_buf()).into())
+            } else if let Err(err) = path.is_dir().then(|| std::fs::read_dir(path)).transpose() {
+                Err(ErrorKind::ReadingDirFailed(err).into())
+            } else {
+                Ok(detect_java(path, max_depth))
+            };
+            (path.to_path_buf(), result)
+        })
+        .collect()
+}
+
+/// Detects available Java runtimes within multiple paths up to a maximum depth, whose major versions are in the given range.
+///
+/// It's useful for build tools requiring something like "Java 11 to 17".
+///
+/// # Parameters
+///
+/// * `paths`: The paths to search for Java runtimes.
+/// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+/// * `range`: The inclusive range of major versions, like `11..=17`
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_java_in_range(&["/usr/lib/jvm".as_ref()], 2, 11..=17);
+/// println!("Java 11 to 17: {:?}", runtimes);
+/// ```
+pub fn detect_java_in_range(
+    paths: &[&Path],
+    max_depth: usize,
+    range: RangeInclusive<u32>,
+) -> Vec<JavaRuntime> {
+    let mut runtimes = detect_java_in_paths(paths, max_depth);
+    runtimes.retain(|runtime| range.contains(&runtime.get_major_version()));
+    runtimes
+}
+
+/// Detects available Java runtimes within multiple paths up to a maximum depth, and selects the newest one.
+///
+/// Runtimes are compared by [`JavaRuntime::sort_key`], so of the same version, the one with the greatest path is selected.
+///
+/// # Returns
+///
+/// * `Some(JavaRuntime)` of the greatest version.
+/// * `None` if no runtime is found.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// if let Some(runtime) = detector::find_best(&["/usr/lib/jvm".as_ref()], 2) {
+///     println!("Newest Java runtime: {:?}", runtime.get_executable());
+/// }
+/// ```
+pub fn find_best(paths: &[&Path], max_depth: usize) -> Option<JavaRuntime> {
+    detect_java_in_paths(paths, max_depth)
+        .into_iter()
+        .max_by(|a, b| {
+            a.sort_key()
+                .cmp(&b.sort_key())
+                .then_with(|| a.get_executable().cmp(b.get_executable()))
+        })
+}
+
+/// Detects available Java runtimes within multiple paths up to a maximum depth, scanning each path on its own thread.
+///
+/// Runtimes within the same path are probed one by one, so that the load on each mount is bounded,
+/// while a slow path (e.g. a network mount) doesn't hold up the others.
+///
+/// # Parameters
+///
+/// * `paths`: The paths to search for Java runtimes.
+/// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+///
+/// # Returns
+///
+/// A vector containing all detected Java runtimes, in the order of the given paths.
+/// Runtimes found through different paths that resolve to the same executable file are reported only once.
+///
+/// # Panics
+///
+/// If the thread scanning any of the paths panics, the panic is resumed on the calling thread.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_java_in_paths_parallel_by_root(&["/usr/lib/jvm".as_ref(), "/mnt/nfs".as_ref()], 2);
+/// println!("Detected Java runtimes: {:?}", runtimes);
+/// ```
+pub fn detect_java_in_paths_parallel_by_root(
+    paths: &[&Path],
+    max_depth: usize,
+) -> Vec<JavaRuntime> {
+    let config = DetectorConfig::new().max_depth(max_depth);
+    let mut runtimes = std::thread::scope(|scope| {
+        let handles = paths
+            .iter()
+            .map(|&path| {
+                let config = &config;
+                scope.spawn(move || config.detect(path))
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+            .collect::<Vec<JavaRuntime>>()
+    });
+    dedup_by_canonical_path(&mut runtimes);
+    runtimes
+}
+
+/// Detects available Java runtimes within the specified path, probing the java executable files in parallel.
+///
+/// The entries are walked first, then checked with [`rayon`] since each check may spawn a `java -version` subprocess.
+///
+/// Requires the `rayon` feature.
+///
+/// # Returns
+///
+/// A vector containing all detected Java runtimes, sorted by the path of java executable file.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-parallel");
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// // 8 java homes in `root`
+/// for i in 0..8 {
+///     let bin = root.join(format!("jdk-{}", i)).join("bin");
+/// #   fs::create_dir_all(&bin).unwrap();
+/// #   fs::write(bin.join("java"), script).unwrap();
+/// #   fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// }
+///
+/// let runtimes = detector::detect_java_parallel(&root, 2);
+/// assert_eq!(runtimes.len(), 8);
+/// assert_eq!(runtimes[0].get_executable(), root.join("jdk-0/bin/java"));
+/// assert_eq!(runtimes[7].get_executable(), root.join("jdk-7/bin/java"));
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn detect_java_parallel(path: &Path, max_depth: usize) -> Vec<JavaRuntime> {
+    use rayon::prelude::*;
+
+    let config = DetectorConfig::new().max_depth(max_depth);
+    let entries = config.entries(path).collect::<Vec<DirEntry>>();
+    let mut runtimes = entries
+        .par_iter()
+        .filter_map(|entry| config.detect_bin_dir(entry.path()))
+        .collect::<Vec<JavaRuntime>>();
+    runtimes.sort_by(|a, b| a.get_executable().cmp(b.get_executable()));
+    runtimes
+}
+
+/// Serializes the given Java runtimes into pretty-printed JSON.
+///
+/// Requires the `serde` feature, which is enabled by default.
+///
+/// # Errors
+///
+/// * [`ErrorKind::Serialization`] if serialization failed
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+/// use java_runtimes::JavaRuntime;
+///
+/// let runtimes = vec![
+///     JavaRuntime::new("linux", "/jdk-17/bin/java".as_ref(), "17.0.4.1").unwrap(),
+///     JavaRuntime::new("linux", "/jdk-21/bin/java".as_ref(), "21.0.1").unwrap(),
+/// ];
+/// let json = detector::to_json(&runtimes).unwrap();
+/// assert!(json.contains("\n  {\n    \"os\": \"linux\","));
+///
+/// let deserialized: Vec<JavaRuntime> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(deserialized, runtimes);
+/// assert_eq!(deserialized[1].get_version_string(), "21.0.1");
+/// ```
+#[cfg(feature = "serde")]
+pub fn to_json(runtimes: &[JavaRuntime]) -> Result<String, Error> {
+    serde_json::to_string_pretty(runtimes).map_err(|err| ErrorKind::Serialization(err).into())
+}
+
+/// Detects available Java runtimes within multiple paths up to a maximum depth, and records statistics of the scan.
+///
+/// See [`DetectorConfig::report`]
+pub fn detect_report(paths: &[&Path], max_depth: usize) -> DetectionReport {
+    DetectorConfig::new().max_depth(max_depth).report(paths)
+}
+
+/// Detects available Java runtimes within multiple paths up to a maximum depth and appends them to the given vector.
+///
+/// # Parameters
+///
+/// * `runtimes`: Vector to contain detected Java runtimes.
+/// * `paths`: The paths to search for Java runtimes.
+/// * `max_depth`: Maximum depth to search for Java runtimes (see [`WalkDir::max_depth`]).
+///
+/// # Returns
+///
+/// The number of new Java runtimes added to the vector.
+pub fn gather_java_in_paths(
+    runtimes: &mut Vec<JavaRuntime>,
+    paths: &[&Path],
+    max_depth: usize,
+) -> usize {
+    paths
+        .iter()
+        .map(|&path| gather_java(runtimes, path, max_depth))
+        .sum::<usize>()
+}
+
+/// Directories where Java runtimes are commonly installed on Linux and other unix-like systems, see [`detect_java_in_default_locations`]
+///
+/// Windows and macOS have their own lists, see `detect_java_in_windows_default_locations` and `detect_java_in_macos`.
+#[cfg(target_os = "linux")]
+const DEFAULT_LOCATIONS: &[&str] = &["/usr/lib/jvm", "/opt"];
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+const DEFAULT_LOCATIONS: &[&str] = &[];
+
+/// Depth of the java executable files in [`DEFAULT_LOCATIONS`], like `<location>/<home>/bin/java`
+#[cfg(not(any(windows, target_os = "macos")))]
+const DEFAULT_LOCATIONS_MAX_DEPTH: usize = 2;
+
+/// Detects available Java runtimes in the directories where they are commonly installed on the current os.
+///
+/// * Linux: `/usr/lib/jvm`, `/opt`
+/// * Windows: the directories of known vendors, see `detect_java_in_windows_default_locations`
+/// * macOS: the system, user and Homebrew directories, see `detect_java_in_macos`
+///
+/// Unlike `detect_java_in_macos`, `/usr/libexec/java_home` is not executed.
+///
+/// [`detect_all`] searches these locations too, along with environment variables, version managers and Gradle settings.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_java_in_default_locations();
+/// for runtime in &runtimes {
+///     assert!(runtime.get_executable().is_file());
+/// }
+/// ```
+pub fn detect_java_in_default_locations() -> Vec<JavaRuntime> {
+    detect_java_in_os_default_locations()
+}
+
+#[cfg(windows)]
+fn detect_java_in_os_default_locations() -> Vec<JavaRuntime> {
+    detect_java_in_windows_default_locations()
+}
+
+#[cfg(target_os = "macos")]
+fn detect_java_in_os_default_locations() -> Vec<JavaRuntime> {
+    detect_java_in_macos_default_dirs()
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn detect_java_in_os_default_locations() -> Vec<JavaRuntime> {
+    let paths = DEFAULT_LOCATIONS
+        .iter()
+        .map(Path::new)
+        .collect::<Vec<&Path>>();
+    let mut runtimes: Vec<JavaRuntime> = vec![];
+    gather_java_in_paths(&mut runtimes, &paths, DEFAULT_LOCATIONS_MAX_DEPTH);
+    dedup_by_canonical_path(&mut runtimes);
+    runtimes
+}
+
+/// Detects available Java runtimes in the standard macOS locations.
+///
+/// The java homes listed by `/usr/libexec/java_home -X` are detected first.
+/// Then it searches `.jdk` bundles in the directories below:
+///
+/// * `/Library/Java/JavaVirtualMachines`
+/// * `~/Library/Java/JavaVirtualMachines`
+/// * `<homebrew prefix>/opt/*/libexec`, where the prefix is `$HOMEBREW_PREFIX`, `/opt/homebrew` or `/usr/local`
+///
+/// Bundles symlinked into the standard directories by Homebrew are reported only once.
+#[cfg(target_os = "macos")]
+pub fn detect_java_in_macos() -> Vec<JavaRuntime> {
+    detect_java_in_homes(&macos_java_homes())
+}
+
+/// Collects the java homes listed by `/usr/libexec/java_home -X` and the ones in the standard macOS directories
+#[cfg(target_os = "macos")]
+fn macos_java_homes() -> Vec<PathBuf> {
+    let mut java_homes = Command::new("/usr/libexec/java_home")
+        .arg("-X")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse_java_home_plist(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default();
+    java_homes.extend(macos_default_dirs_java_homes());
+    java_homes
+}
+
+/// Detects available Java runtimes in the standard macOS directories, see [`detect_java_in_macos_dirs`]
+#[cfg(target_os = "macos")]
+fn detect_java_in_macos_default_dirs() -> Vec<JavaRuntime> {
+    detect_java_in_homes(&macos_default_dirs_java_homes())
+}
+
+/// Collects the `.jdk` bundles in the standard macOS directories
+#[cfg(target_os = "macos")]
+fn macos_default_dirs_java_homes() -> Vec<PathBuf> {
+    let mut jvm_dirs = vec![PathBuf::from("/Library/Java/JavaVirtualMachines")];
+    if let Some(home) = std::env::var_os("HOME") {
+        jvm_dirs.push(Path::new(&home).join("Library/Java/JavaVirtualMachines"));
+    }
+
+    let mut homebrew_prefixes = vec![];
+    if let Some(prefix) = std::env::var_os("HOMEBREW_PREFIX") {
+        homebrew_prefixes.push(PathBuf::from(prefix));
+    }
+    homebrew_prefixes.push(PathBuf::from("/opt/homebrew"));
+    homebrew_prefixes.push(PathBuf::from("/usr/local"));
+
+    let jvm_dirs = jvm_dirs
+        .iter()
+        .map(PathBuf::as_path)
+        .collect::<Vec<&Path>>();
+    let homebrew_prefixes = homebrew_prefixes
+        .iter()
+        .map(PathBuf::as_path)
+        .collect::<Vec<&Path>>();
+    macos_dirs_java_homes(&jvm_dirs, &homebrew_prefixes)
+}
+
+/// Parses the java homes from the XML plist printed by `/usr/libexec/java_home -X` on macOS.
+///
+/// The plist is an array of dicts, one for each installed JVM, and the java home is the `JVMHomePath` entry.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_java_home_plist(plist: &str) -> Vec<PathBuf> {
+    Regex::new(r"<key>JVMHomePath</key>\s*<string>([^<]*)</string>")
+        .unwrap()
+        .captures_iter(plist)
+        .map(|captures| {
+            let escaped = &captures[1];
+            let unescaped = escaped
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&");
+            PathBuf::from(unescaped)
+        })
+        .collect()
+}
+
+/// Registry keys written by Java installers on Windows, each with a subkey per version.
+///
+/// Each version subkey holds the java home directory in its `JavaHome` value, like
+/// `HKEY_LOCAL_MACHINE\SOFTWARE\JavaSoft\JDK\17\JavaHome`.
+#[cfg(all(windows, feature = "registry"))]
+const REGISTRY_JAVA_KEYS: [&str; 4] = [
+    r"SOFTWARE\JavaSoft\JDK",
+    r"SOFTWARE\JavaSoft\JRE",
+    r"SOFTWARE\JavaSoft\Java Development Kit",
+    r"SOFTWARE\JavaSoft\Java Runtime Environment",
+];
+
+/// Detects available Java runtimes registered in the Windows registry.
+///
+/// It reads the `JavaHome` value of every version subkey under the keys below,
+/// in both `HKEY_LOCAL_MACHINE` and `HKEY_CURRENT_USER`, and in both the 64-bit and the 32-bit (`WOW6432Node`) views:
+///
+/// * `SOFTWARE\JavaSoft\JDK`, written by installers of Java 9 and later
+/// * `SOFTWARE\JavaSoft\JRE`
+/// * `SOFTWARE\JavaSoft\Java Development Kit`, written by legacy installers
+/// * `SOFTWARE\JavaSoft\Java Runtime Environment`
+///
+/// Legacy installers register the same home under several subkeys like `1.8` and `1.8.0_301`,
+/// such runtimes are reported only once.
+///
+/// Requires the `registry` feature, which is enabled by default.
+///
+/// # Examples
+///
+/// It returns an empty vector if there is no java registered.
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_java_in_windows_registry();
+/// for runtime in &runtimes {
+///     assert!(runtime.get_executable().is_file());
+/// }
+/// ```
+#[cfg(all(windows, feature = "registry"))]
+pub fn detect_java_in_windows_registry() -> Vec<JavaRuntime> {
+    detect_java_in_homes(&read_registry_java_homes())
+}
+
+/// Reads the java homes from every view of the registry keys, see [`detect_java_in_windows_registry`]
+#[cfg(all(windows, feature = "registry"))]
+fn read_registry_java_homes() -> Vec<PathBuf> {
+    use winreg::enums::{
+        HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_32KEY, KEY_WOW64_64KEY,
+    };
+    use winreg::RegKey;
+
+    let mut java_homes = vec![];
+    for hive in [HKEY_LOCAL_MACHINE, HKEY_CURRENT_USER] {
+        let hive = RegKey::predef(hive);
+        // 32-bit installers write to the `WOW6432Node` view
+        for view in [KEY_WOW64_64KEY, KEY_WOW64_32KEY] {
+            for key in REGISTRY_JAVA_KEYS {
+                let Ok(key) = hive.open_subkey_with_flags(key, KEY_READ | view) else {
+                    continue;
+                };
+                let subkeys = key.enum_keys().filter_map(Result::ok).map(|version| {
+                    let java_home = key
+                        .open_subkey_with_flags(&version, KEY_READ | view)
+                        .and_then(|subkey| subkey.get_value::<String, _>("JavaHome"))
+                        .ok();
+                    (version, java_home)
+                });
+                java_homes.extend(registry_java_homes(subkeys));
+            }
+        }
+    }
+    java_homes
+}
+
+/// Gets the java homes from the version subkeys of a registry key like `SOFTWARE\JavaSoft\JDK`
+///
+/// Each pair is the name of a subkey and its `JavaHome` value, if any.
+///
+/// * Only version subkeys like `17` or `1.8.0_301` are read
+/// * Subkeys without a `JavaHome` value are skipped
+/// * Legacy installers register the same home under several subkeys like `1.8` and `1.8.0_301`,
+///   it's returned only once, ignoring case and trailing separators like Windows does
+#[cfg_attr(not(all(windows, feature = "registry")), allow(dead_code))]
+fn registry_java_homes(
+    subkeys: impl IntoIterator<Item = (String, Option<String>)>,
+) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    subkeys
+        .into_iter()
+        .filter(|(version, _)| version.starts_with(|c: char| c.is_ascii_digit()))
+        .filter_map(|(_, java_home)| java_home)
+        .map(|java_home| java_home.trim().to_string())
+        .filter(|java_home| !java_home.is_empty())
+        .filter(|java_home| seen.insert(java_home.trim_end_matches(['\\', '/']).to_lowercase()))
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Directories that Java installers on Windows create under `Program Files`, each containing java homes
+const WINDOWS_VENDOR_DIRS: [&str; 9] = [
+    "Java",
+    "Eclipse Adoptium",
+    "Eclipse Foundation",
+    "AdoptOpenJDK",
+    "Microsoft",
+    "Zulu",
+    "Amazon Corretto",
+    "BellSoft",
+    "Semeru",
+];
+
+/// Detects available Java runtimes in the default install locations on Windows.
+///
+/// It searches the directories of known vendors in `%ProgramFiles%`, `%ProgramFiles(x86)%`
+/// and the per-user `%LOCALAPPDATA%`, see [`detect_java_in_windows_dirs`].
+#[cfg(windows)]
+pub fn detect_java_in_windows_default_locations() -> Vec<JavaRuntime> {
+    detect_java_in_homes(&windows_default_java_homes())
+}
+
+/// Collects the java homes in the default Windows install locations, see [`detect_java_in_windows_default_locations`]
+#[cfg(windows)]
+fn windows_default_java_homes() -> Vec<PathBuf> {
+    let program_files = ["ProgramFiles", "ProgramFiles(x86)"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .collect::<Vec<PathBuf>>();
+    let local_app_data = std::env::var_os("LOCALAPPDATA").map(PathBuf::from);
+
+    let program_files = program_files
+        .iter()
+        .map(PathBuf::as_path)
+        .collect::<Vec<&Path>>();
+    windows_dirs_java_homes(&program_files, local_app_data.as_deref())
+}
+
+/// Detects available Java runtimes in Windows style directories.
+///
+/// It searches java homes in the directories below:
+///
+/// * `<program files>\<vendor>`, like `C:\Program Files\Eclipse Adoptium\jdk-17.0.4.1+1`
+/// * `<local app data>\Programs\<vendor>`, where per-user installers put runtimes
+/// * `<local app data>\Microsoft\WinGet\Packages\<package>`, where winget puts portable runtimes
+///
+/// Runtimes found through different paths that resolve to the same executable file are reported only once.
+///
+/// # Parameters
+///
+/// * `program_files`: Program files directories, like `C:\Program Files`.
+/// * `local_app_data`: The local app data directory of current user, like `C:\Users\Alice\AppData\Local`.
+///
+/// # Returns
+///
+/// A vector containing all detected Java runtimes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{env, fs, process};
+/// # // Copies of this program act as java, printing the version in the name of java home
+/// # if env::args().any(|arg| arg == "-version") {
+/// #     let exe = env::current_exe().unwrap();
+/// #     let home = exe.parent().unwrap().parent().unwrap().file_name().unwrap().to_string_lossy().into_owned();
+/// #     eprintln!("openjdk version \"{}\"", home.trim_start_matches("jdk-").split('+').next().unwrap());
+/// #     process::exit(0);
+/// # }
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-windows-dirs");
+/// # let fake_java = |home: &str| {
+/// #     let bin = root.join(home).join("bin");
+/// #     fs::create_dir_all(&bin).unwrap();
+/// #     let java = bin.join(format!("java{}", env::consts::EXE_SUFFIX));
+/// #     fs::copy(env::current_exe().unwrap(), java).unwrap();
+/// # };
+/// # fake_java("Program Files/Java/jdk-1.8.0_333");
+/// # fake_java("Local/Programs/Eclipse Adoptium/jdk-17.0.4.1+1");
+/// # fake_java("Local/Microsoft/WinGet/Packages/EclipseAdoptium.Temurin.21.JDK/jdk-21.0.1+12");
+/// let runtimes = detector::detect_java_in_windows_dirs(
+///     &[root.join("Program Files").as_ref()],
+///     Some(&root.join("Local")),
+/// );
+/// let mut versions = runtimes
+///     .iter()
+///     .map(|runtime| runtime.get_version_string())
+///     .collect::<Vec<&str>>();
+/// versions.sort();
+/// assert_eq!(versions, ["1.8.0_333", "17.0.4.1", "21.0.1"]);
+/// ```
+pub fn detect_java_in_windows_dirs(
+    program_files: &[&Path],
+    local_app_data: Option<&Path>,
+) -> Vec<JavaRuntime> {
+    detect_java_in_homes(&windows_dirs_java_homes(program_files, local_app_data))
+}
+
+/// Collects the java homes in the given Windows install locations, see [`detect_java_in_windows_dirs`]
+fn windows_dirs_java_homes(program_files: &[&Path], local_app_data: Option<&Path>) -> Vec<PathBuf> {
+    let mut vendor_dirs = vec![];
+    for &dir in program_files {
+        vendor_dirs.extend(WINDOWS_VENDOR_DIRS.iter().map(|vendor| dir.join(vendor)));
+    }
+    let mut package_dirs = vec![];
+    if let Some(local_app_data) = local_app_data {
+        let programs = local_app_data.join("Programs");
+        vendor_dirs.extend(
+            WINDOWS_VENDOR_DIRS
+                .iter()
+                .map(|vendor| programs.join(vendor)),
+        );
+        package_dirs.push(
+            local_app_data
+                .join("Microsoft")
+                .join("WinGet")
+                .join("Packages"),
+        );
+    }
+
+    // winget packages contain the java home in a subdirectory
+    vendor_dirs
+        .iter()
+        .flat_map(|dir| read_dir_paths(dir))
+        .chain(
+            package_dirs
+                .iter()
+                .flat_map(|dir| read_dir_paths(dir))
+                .flat_map(|package| read_dir_paths(&package)),
+        )
+        .collect()
+}
+
+/// Detects available Java runtimes in macOS style directories.
+///
+/// Runtimes found through different paths that resolve to the same executable file are reported only once.
+///
+/// # Parameters
+///
+/// * `jvm_dirs`: Directories containing `.jdk` bundles, like `/Library/Java/JavaVirtualMachines`.
+/// * `homebrew_prefixes`: Homebrew prefixes, like `/opt/homebrew`. Bundles are searched in `<prefix>/opt/*/libexec`.
+///
+/// # Returns
+///
+/// A vector containing all detected Java runtimes.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-macos");
+/// # let bundle = root.join("homebrew/opt/openjdk/libexec/openjdk.jdk");
+/// # let bin = bundle.join("Contents/Home/bin");
+/// # fs::create_dir_all(&bin).unwrap();
+/// # let script = "#!/bin/sh\necho 'openjdk version \"21.0.1\" 2023-10-17' >&2\n";
+/// # fs::write(bin.join("java"), script).unwrap();
+/// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// # let jvm_dir = root.join("JavaVirtualMachines");
+/// # fs::create_dir_all(&jvm_dir).unwrap();
+/// # let _ = std::os::unix::fs::symlink(&bundle, jvm_dir.join("openjdk.jdk"));
+/// // `JavaVirtualMachines/openjdk.jdk` is a symlink to the bundle in homebrew prefix
+/// let runtimes = detector::detect_java_in_macos_dirs(
+///     &[jvm_dir.as_ref()],
+///     &[root.join("homebrew").as_ref()],
+/// );
+/// assert_eq!(runtimes.len(), 1);
+/// assert_eq!(runtimes[0].get_version_string(), "21.0.1");
+/// # }
+/// ```
+pub fn detect_java_in_macos_dirs(
+    jvm_dirs: &[&Path],
+    homebrew_prefixes: &[&Path],
+) -> Vec<JavaRuntime> {
+    detect_java_in_homes(&macos_dirs_java_homes(jvm_dirs, homebrew_prefixes))
+}
+
+/// Collects the `.jdk` bundles directly inside the jvm directories and the homebrew `opt/*/libexec` directories
+///
+/// Symlinked bundles are followed.
+fn macos_dirs_java_homes(jvm_dirs: &[&Path], homebrew_prefixes: &[&Path]) -> Vec<PathBuf> {
+    let mut java_homes = vec![];
+    for &jvm_dir in jvm_dirs {
+        java_homes.extend(read_dir_paths(jvm_dir));
+    }
+    for &prefix in homebrew_prefixes {
+        for formula in read_dir_paths(&prefix.join("opt")) {
+            java_homes.extend(read_dir_paths(&formula.join("libexec")));
+        }
+    }
+    java_homes
+}
+
+/// Detects Java runtimes in the given java homes, reporting each executable file only once
+fn detect_java_in_homes(java_homes: &[PathBuf]) -> Vec<JavaRuntime> {
+    let mut runtimes = java_homes
+        .iter()
+        .filter_map(|java_home| detect_java_home_dir(java_home))
+        .collect::<Vec<JavaRuntime>>();
+    dedup_by_canonical_path(&mut runtimes);
+    runtimes
+}
+
+/// List the paths of entries in the given directory, or nothing if it can not be read
+fn read_dir_paths(dir: &Path) -> Vec<PathBuf> {
+    match dir.read_dir() {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Removes runtimes reporting the same `java.home` and major version, see [`JavaRuntime::probe_properties`].
+///
+/// A Java 8 JDK contains both `bin/java` and `jre/bin/java`, which both report the inner JRE as `java.home`.
+/// Such runtimes collapse into one, preferring the one with `javac`, i.e. the JDK.
+/// Runtimes whose properties can not be probed are kept.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let jdk = env::temp_dir().join("java-runtimes-doctest-reported-home/jdk1.8.0_333");
+/// # let script = format!(r#"#!/bin/sh
+/// # case "$*" in *-XshowSettings:properties*) echo '    java.home = {}/jre' >&2 ;; esac
+/// # echo 'java version "1.8.0_333"' >&2
+/// # "#, jdk.display());
+/// # for bin in [jdk.join("bin"), jdk.join("jre/bin")] {
+/// #     fs::create_dir_all(&bin).unwrap();
+/// #     fs::write(bin.join("java"), &script).unwrap();
+/// #     fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// # }
+/// # fs::write(jdk.join("bin/javac"), "").unwrap();
+/// // jdk1.8.0_333/bin/java
+/// // jdk1.8.0_333/jre/bin/java
+/// let mut runtimes = vec![
+///     detector::detect_java_home_dir(&jdk.join("jre")).unwrap(),
+///     detector::detect_java_home_dir(&jdk).unwrap(),
+/// ];
+/// detector::dedup_by_reported_home(&mut runtimes);
+/// assert_eq!(runtimes.len(), 1);
+/// assert_eq!(runtimes[0].get_executable(), jdk.join("bin/java"));
+/// # }
+/// ```
+pub fn dedup_by_reported_home(runtimes: &mut Vec<JavaRuntime>) {
+    let mut deduped: Vec<JavaRuntime> = vec![];
+    let mut indices: HashMap<(String, u32), usize> = HashMap::new();
+    for runtime in runtimes.drain(..) {
+        let reported_home = runtime
+            .probe_properties()
+            .ok()
+            .and_then(|mut properties| properties.remove("java.home"));
+        let Some(reported_home) = reported_home else {
+            deduped.push(runtime);
+            continue;
+        };
+        match indices.entry((reported_home, runtime.get_major_version())) {
+            Entry::Occupied(entry) => {
+                let kept = &mut deduped[*entry.get()];
+                if runtime.is_jdk() && !kept.is_jdk() {
+                    *kept = runtime;
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(deduped.len());
+                deduped.push(runtime);
+            }
+        }
+    }
+    *runtimes = deduped;
+}
+
+/// Removes runtimes whose executable files resolve to the same canonical path, keeping the first one
+fn dedup_by_canonical_path(runtimes: &mut Vec<JavaRuntime>) {
+    let mut seen = HashSet::new();
+    runtimes.retain(|runtime| {
+        seen.insert(
+            runtime
+                .canonical_path()
+                .unwrap_or_else(|_| runtime.get_executable().to_path_buf()),
+        )
+    });
+}
+
+/// Detects available Java runtimes with every strategy available for the current os.
+///
+/// The strategies are:
+///
+/// * Environment variables, see [`detect_java_in_environments`]
+/// * Default install locations of the current os, see [`detect_java_in_default_locations`]
+/// * macOS java homes listed by `/usr/libexec/java_home -X`, see `detect_java_in_macos`
+/// * Windows registry, see `detect_java_in_windows_registry` (with the `registry` feature)
+/// * Version managers, see [`detect_java_in_sdkman`] and [`detect_java_in_asdf`]
+/// * Gradle settings of the project in the current directory, see [`detect_java_from_gradle_properties`]
+///
+/// Runtimes found by multiple strategies are reported only once.
+///
+/// # Returns
+///
+/// A vector containing all detected Java runtimes, sorted from the newest version to the oldest.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let runtimes = detector::detect_all();
+/// println!("Detected Java runtimes: {:?}", runtimes);
+/// ```
+pub fn detect_all() -> Vec<JavaRuntime> {
+    detect_all_with_errors().0
+}
+
+/// Detects available Java runtimes like [`detect_all`], and collects the non-fatal errors during the detection.
+///
+/// Errors include:
+///
+/// * Environment variables like `JAVA_HOME` pointing to a missing path
+/// * Directories that can't be read
+/// * Java executable files that fail to report a version, including the ones found by the macOS and Windows strategies
+///
+/// Missing directories in `PATH` are common and not reported.
+///
+/// # Returns
+///
+/// The detected runtimes sorted from the newest version to the oldest, and the errors.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let (runtimes, errors) = detector::detect_all_with_errors();
+/// println!("Detected Java runtimes: {:?}", runtimes);
+/// for err in errors {
+///     println!("warning: {}", err);
+/// }
+/// ```
+pub fn detect_all_with_errors() -> (Vec<JavaRuntime>, Vec<Error>) {
+    let config = DetectorConfig::new().max_depth(1);
+    let mut runtimes: Vec<JavaRuntime> = vec![];
+    let mut errors: Vec<Error> = vec![];
+    for name in JAVA_HOME_VARS {
+        if let Some(path) = std::env::var_os(name).map(PathBuf::from) {
+            if path.exists() {
+                config.gather_with_errors(&mut runtimes, &mut errors, &path);
+            } else {
+                errors.push(
+                    ErrorKind::InvalidEnvironmentVariable {
+                        name: name.to_string(),
+                        path,
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+    if let Some(env_path) = std::env::var_os("PATH") {
+        for path in std::env::split_paths(&env_path).filter(|path| path.exists()) {
+            config.gather_with_errors(&mut runtimes, &mut errors, &path);
+        }
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        let config = DetectorConfig::new().max_depth(DEFAULT_LOCATIONS_MAX_DEPTH);
+        for location in DEFAULT_LOCATIONS.iter().map(Path::new) {
+            if location.exists() {
+                config.gather_with_errors(&mut runtimes, &mut errors, location);
+            }
+        }
+    }
+    for java_home in os_java_homes().into_iter().chain(tool_java_homes()) {
+        match detect_java_home_dir_checked(&java_home) {
+            Some(Ok(runtime)) => runtimes.push(runtime),
+            Some(Err(err)) => errors.push(err),
+            None => {}
+        }
+    }
+
+    dedup_by_canonical_path(&mut runtimes);
+    runtimes.sort_by_key(|runtime| Reverse(runtime.sort_key()));
+    (runtimes, errors)
+}
+
+/// Collects the java homes installed by version managers or configured for build tools, see [`detect_all`]
+fn tool_java_homes() -> Vec<PathBuf> {
+    let mut java_homes = sdkman_java_homes();
+    java_homes.extend(asdf_java_homes());
+    if let Ok(dir) = std::env::current_dir() {
+        java_homes.extend(gradle_java_home(&dir));
+    }
+    java_homes
+}
+
+/// Collects the java homes found by the strategies specific to the current os, see [`detect_all`]
+fn os_java_homes() -> Vec<PathBuf> {
+    #[allow(unused_mut)]
+    let mut java_homes: Vec<PathBuf> = vec![];
+    #[cfg(target_os = "macos")]
+    java_homes.extend(macos_java_homes());
+    #[cfg(all(windows, feature = "registry"))]
+    java_homes.extend(read_registry_java_homes());
+    #[cfg(windows)]
+    java_homes.extend(windows_default_java_homes());
+    java_homes
+}
+
+/// Detects available Java runtimes and collects their major versions, see [`detect_all`].
+///
+/// It's useful for a summary like `Java available: 8, 11, 17, 21`.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// let majors = detector::available_majors();
+/// println!("Java available: {:?}", majors);
+/// ```
+pub fn available_majors() -> BTreeSet<u32> {
+    detect_all()
+        .iter()
+        .map(JavaRuntime::get_major_version)
+        .collect()
+}
+
+/// Detects available Java runtimes from environment variables, with a user-specified one first.
+///
+/// This models a "custom Java path" setting that takes precedence over auto-detection.
+///
+/// # Parameters
+///
+/// * `custom`: Path to a java home, `bin` directory, or java executable file.
+///
+/// # Returns
+///
+/// A vector containing the custom runtime if it's valid, followed by runtimes detected from environment variables
+/// (see [`detect_java_in_environments`]). Duplicates of the custom runtime are removed.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// // Configured by the user
+/// let custom = "/opt/jdk-21".as_ref();
+/// let runtimes = detector::detect_with_override(Some(custom));
+/// println!("Detected Java runtimes: {:?}", runtimes);
+/// ```
+pub fn detect_with_override(custom: Option<&Path>) -> Vec<JavaRuntime> {
+    let mut runtimes: Vec<JavaRuntime> = custom.and_then(detect_java_at).into_iter().collect();
+    runtimes.extend(detect_java_in_environments());
+    dedup_by_canonical_path(&mut runtimes);
+    runtimes
+}
+
+/// Attempts to detect a Java runtime from a path to java home, `bin` directory or java executable file
+fn detect_java_at(path: &Path) -> Option<JavaRuntime> {
+    if path.is_file() {
+        detect_java_exe(path)
+    } else {
+        detect_java_bin_dir(path).or_else(|| detect_java_home_dir(path))
+    }
+}
+
+/// Detects the best Java runtime for running applications, see [`detect_all`].
+///
+/// Runtimes without `javac` (JREs and jlink images) are preferred over JDKs because of their smaller footprint.
+/// Among runtimes of the same kind, the one with the highest major version wins.
+///
+/// # Parameters
+///
+/// * `min_major`: Minimum major version the runtime must have, e.g. `8` for `1.8.0_333`.
+///
+/// # Returns
+///
+/// * `Some(JavaRuntime)` if a runtime meeting the minimum version is found.
+/// * `None` if no such runtime is found.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// if let Some(runtime) = detector::best_jre(17) {
+///     println!("Java runtime to run the app: {:?}", runtime.get_executable());
+/// }
+/// ```
+pub fn best_jre(min_major: u32) -> Option<JavaRuntime> {
+    detect_all()
+        .into_iter()
+        .filter(|runtime| runtime.get_major_version() >= min_major)
+        .min_by_key(|runtime| (runtime.is_jdk(), Reverse(runtime.get_major_version())))
+}
+
+/// Detects the newest Java runtime whose architecture matches the current one, see [`detect_all`].
+///
+/// Native runtimes are preferred over emulated ones, e.g. `aarch64` over `x86_64` on Apple Silicon.
+/// See [`select_native_preferred`] for details.
+pub fn detect_native_preferred(min_major: u32) -> Option<JavaRuntime> {
+    select_native_preferred(detect_all(), min_major, std::env::consts::ARCH)
+}
+
+/// Selects the newest Java runtime whose architecture matches the given host architecture.
+///
+/// The architecture of a runtime is the one reported by java, or read from the `release` file in its java home.
+/// If no runtime matches, the newest one of any architecture is selected.
+///
+/// # Parameters
+///
+/// * `runtimes`: Java runtimes to select from.
+/// * `min_major`: Minimum major version the runtime must have.
+/// * `host_arch`: Architecture of the host, like [`std::env::consts::ARCH`].
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+/// use java_runtimes::JavaRuntime;
+/// use std::{env, fs};
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-native-preferred");
+/// # let runtime = |home: &str, version: &str, os_arch: &str| {
+/// #     fs::create_dir_all(root.join(home)).unwrap();
+/// #     fs::write(root.join(home).join("release"), format!("OS_ARCH=\"{}\"\n", os_arch)).unwrap();
+/// #     JavaRuntime::new("macos", &root.join(home).join("bin/java"), version).unwrap()
+/// # };
+/// let runtimes = vec![
+///     runtime("jdk-21-x64", "21.0.1", "x86_64"),
+///     runtime("jdk-17-arm", "17.0.4.1", "aarch64"),
+/// ];
+///
+/// let selected = detector::select_native_preferred(runtimes.clone(), 11, "aarch64").unwrap();
+/// assert_eq!(selected.get_version_string(), "17.0.4.1");
+///
+/// let selected = detector::select_native_preferred(runtimes.clone(), 11, "riscv64").unwrap();
+/// assert_eq!(selected.get_version_string(), "21.0.1");
+///
+/// assert!(detector::select_native_preferred(runtimes, 25, "aarch64").is_none());
+/// ```
+pub fn select_native_preferred(
+    runtimes: Vec<JavaRuntime>,
+    min_major: u32,
+    host_arch: &str,
+) -> Option<JavaRuntime> {
+    runtimes
+        .into_iter()
+        .filter(|runtime| runtime.get_major_version() >= min_major)
+        .max_by_key(|runtime| (runtime.is_arch(host_arch) == Some(true), runtime.sort_key()))
+}
+
+/// Detects the most recently installed Java runtime, see [`detect_all`].
+///
+/// See [`select_most_recently_installed`] for details.
+pub fn detect_most_recently_installed() -> Option<JavaRuntime> {
+    select_most_recently_installed(detect_all())
+}
+
+/// Selects the Java runtime whose java home has the newest modification time, regardless of versions.
+///
+/// It matches the "JDK I just installed", even when it's not the newest version.
+/// Runtimes whose java home can not be read are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// use java_runtimes::detector;
+/// use java_runtimes::JavaRuntime;
+/// use std::fs::{self, File};
+/// use std::time::{Duration, SystemTime};
+/// use std::env;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-recently-installed");
+/// # let runtime = |home: &str, version: &str, age_days: u64| {
+/// #     fs::create_dir_all(root.join(home).join("bin")).unwrap();
+/// #     let modified = SystemTime::now() - Duration::from_secs(age_days * 24 * 60 * 60);
+/// #     File::open(root.join(home)).unwrap().set_modified(modified).unwrap();
+/// #     JavaRuntime::new("linux", &root.join(home).join("bin/java"), version).unwrap()
+/// # };
+/// let runtimes = vec![
+///     runtime("jdk-21", "21.0.1", 30),
+///     runtime("jdk-17", "17.0.8", 1),
+///     runtime("jdk-11", "11.0.2", 365),
+/// ];
+///
+/// let selected = detector::select_most_recently_installed(runtimes).unwrap();
+/// assert_eq!(selected.get_version_string(), "17.0.8");
+/// # }
+/// ```
+pub fn select_most_recently_installed(runtimes: Vec<JavaRuntime>) -> Option<JavaRuntime> {
+    runtimes
+        .into_iter()
+        .filter_map(|runtime| {
+            let modified = runtime.get_home()?.metadata().ok()?.modified().ok()?;
+            Some((modified, runtime))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, runtime)| runtime)
+}
+
+/// Detects the best Java runtime and runs the given jar file with it.
+///
+/// The runtime is selected by [`detect_native_preferred`], then `java -jar <jar> <args>` is executed,
+/// with stdin, stdout and stderr inherited from the current process.
+///
+/// # Parameters
+///
+/// * `jar`: Path to the jar file.
+/// * `min_major`: Minimum major version the runtime must have, e.g. `8` for `1.8.0_333`.
+/// * `args`: Arguments passed to the main class.
+///
+/// # Returns
+///
+/// The exit status of java.
+///
+/// # Errors
+///
+/// * [`std::io::ErrorKind::NotFound`] if no runtime meeting the minimum version is found.
+/// * Errors from [`Command::status`] if failed to execute java.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector;
+///
+/// match detector::run_jar("app.jar".as_ref(), 17, &["--help"]) {
+///     Ok(status) => println!("The app exited with {}", status),
+///     Err(err) => println!("Failed to run the app: {}", err),
+/// }
+/// ```
+pub fn run_jar(jar: &Path, min_major: u32, args: &[&str]) -> std::io::Result<ExitStatus> {
+    let runtime = detect_native_preferred(min_major).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No java runtime of version {} or later found", min_major),
+        )
+    })?;
+    Command::new(runtime.get_executable())
+        .arg("-jar")
+        .arg(jar)
+        .args(args)
+        .status()
+}
+
+/// Enum [`RuntimeKind`] tells whether a java runtime can compile java sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeKind {
+    /// Runtime with `javac`
+    Jdk,
+    /// Runtime without `javac`, like a JRE or a jlink image
+    Jre,
+}
+
+/// Constraints for selecting Java runtimes, see [`rank`].
+///
+/// * Major version range, vendor and architecture are required: runtimes not meeting them are excluded.
+/// * The preferred kind is a soft constraint: runtimes of that kind get a higher score.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector::{Constraints, RuntimeKind};
+///
+/// let constraints = Constraints::new()
+///     .min_major(11)
+///     .max_major(17)
+///     .vendor("Eclipse Adoptium")
+///     .arch("aarch64")
+///     .prefer(RuntimeKind::Jdk);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Constraints {
+    min_major: Option<u32>,
+    max_major: Option<u32>,
+    vendor: Option<String>,
+    arch: Option<String>,
+    prefer: Option<RuntimeKind>,
+}
+
+impl Constraints {
+    /// Score for matching the preferred kind
+    const PREFERRED_KIND_SCORE: i32 = 100;
+
+    /// Create constraints that every runtime meets
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the major version to be at least the given one
+    pub fn min_major(mut self, major: u32) -> Self {
+        self.min_major = Some(major);
+        self
+    }
+
+    /// Require the major version to be at most the given one
+    pub fn max_major(mut self, major: u32) -> Self {
+        self.max_major = Some(major);
+        self
+    }
+
+    /// Require the vendor to contain the given name, ignoring case
+    ///
+    /// The vendor is read from the `IMPLEMENTOR` entry of the `release` file in java home.
+    pub fn vendor(mut self, vendor: &str) -> Self {
+        self.vendor = Some(vendor.to_string());
+        self
+    }
+
+    /// Require the architecture to be the given one, like `aarch64` or `x86_64`
+    ///
+    /// The architecture is read from the `OS_ARCH` entry of the `release` file in java home.
+    pub fn arch(mut self, arch: &str) -> Self {
+        self.arch = Some(arch.to_string());
+        self
+    }
+
+    /// Prefer runtimes of the given kind
+    pub fn prefer(mut self, kind: RuntimeKind) -> Self {
+        self.prefer = Some(kind);
+        self
+    }
+
+    /// Compute the suitability score of the runtime, or `None` if it does not meet the requirements
+    ///
+    /// The score is the major version, plus [`Constraints::PREFERRED_KIND_SCORE`] if it's of the preferred kind.
+    fn score(&self, runtime: &JavaRuntime) -> Option<i32> {
+        let major = runtime.get_major_version();
+        if self.min_major.is_some_and(|min| major < min)
+            || self.max_major.is_some_and(|max| major > max)
+        {
+            return None;
+        }
+        if let Some(vendor) = &self.vendor {
+            let known_vendor = runtime.known_vendor()?.to_lowercase();
+            if !known_vendor.contains(&vendor.to_lowercase()) {
+                return None;
+            }
+        }
+        if let Some(arch) = &self.arch {
+            if runtime.is_arch(arch) != Some(true) {
+                return None;
+            }
+        }
+
+        let kind = if runtime.is_jdk() {
+            RuntimeKind::Jdk
+        } else {
+            RuntimeKind::Jre
+        };
+        let kind_score = if self.prefer == Some(kind) {
+            Self::PREFERRED_KIND_SCORE
+        } else {
+            0
+        };
+        Some(major as i32 + kind_score)
+    }
+}
+
+/// Detects Java runtimes and ranks them by suitability for the given constraints, see [`detect_all`].
+///
+/// See [`rank_runtimes`] for details.
+pub fn rank(constraints: Constraints) -> Vec<(JavaRuntime, i32)> {
+    rank_runtimes(detect_all(), &constraints)
+}
+
+/// Ranks Java runtimes by suitability for the given constraints.
+///
+/// # Returns
+///
+/// Runtimes meeting the requirements of the constraints with their scores, sorted by score in descending order.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::detector::{self, Constraints, RuntimeKind};
+/// use java_runtimes::JavaRuntime;
+/// use std::{env, fs};
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-rank");
+/// # let runtime = |home: &str, version: &str, vendor: &str, jdk: bool| {
+/// #     fs::create_dir_all(root.join(home).join("bin")).unwrap();
+/// #     let release = format!("IMPLEMENTOR=\"{}\"\nOS_ARCH=\"x86_64\"\n", vendor);
+/// #     fs::write(root.join(home).join("release"), release).unwrap();
+/// #     if jdk {
+/// #         fs::write(root.join(home).join("bin/javac"), "").unwrap();
+/// #     }
+/// #     JavaRuntime::new("linux", &root.join(home).join("bin/java"), version).unwrap()
+/// # };
+/// let runtimes = vec![
+///     runtime("temurin-jre-17", "17.0.4.1", "Eclipse Adoptium", false),
+///     runtime("temurin-jdk-11", "11.0.2", "Eclipse Adoptium", true),
+///     runtime("temurin-jdk-21", "21.0.1", "Eclipse Adoptium", true),
+///     runtime("corretto-jdk-17", "17.0.4.1", "Amazon.com Inc.", true),
+/// ];
+///
+/// let constraints = Constraints::new()
+///     .min_major(11)
+///     .max_major(17)
+///     .vendor("adoptium")
+///     .arch("x86_64")
+///     .prefer(RuntimeKind::Jdk);
+/// let ranked = detector::rank_runtimes(runtimes, &constraints);
+///
+/// // Java 21 is out of range, and Corretto is not the required vendor
+/// assert_eq!(ranked.len(), 2);
+/// // Preferring JDK outweighs a newer version
+/// assert_eq!(ranked[0].0.get_version_string(), "11.0.2");
+/// assert_eq!(ranked[1].0.get_version_string(), "17.0.4.1");
+/// assert!(ranked[0].1 > ranked[1].1);
+/// ```
+pub fn rank_runtimes(
+    runtimes: Vec<JavaRuntime>,
+    constraints: &Constraints,
+) -> Vec<(JavaRuntime, i32)> {
+    let mut ranked = runtimes
+        .into_iter()
+        .filter_map(|runtime| {
+            let score = constraints.score(&runtime)?;
+            Some((runtime, score))
+        })
+        .collect::<Vec<(JavaRuntime, i32)>>();
+    ranked.sort_by_key(|(_, score)| Reverse(*score));
+    ranked
+}
+
+/// Attempts to detect a Java runtime from the given path.
+///
+/// # Returns
+///
+/// * `Some(JavaRuntime)` if the given path points to an available Java executable file.
+/// * `None` if the given path is not an available Java executable file.
+pub fn detect_java_exe(path: &Path) -> Option<JavaRuntime> {
+    JavaRuntime::from_executable(path).ok()
+}
+
+/// Attempts to detect a Java runtime from the given directory path.
+///
+/// # Returns
+///
+/// * `Some(JavaRuntime)` if the given path is a directory containing the Java executable file.
+/// * `None` if the given path is not a directory containing the Java executable file.
+pub fn detect_java_bin_dir(bin_dir: &Path) -> Option<JavaRuntime> {
+    detect_java_exe(&bin_dir.join(JavaRuntime::get_java_executable_name()))
+}
+
+/// Attempts to detect a Java runtime from the given directory path, without executing java.
+///
+/// Only the name and location of the Java executable file are checked, so it's much faster than [`detect_java_bin_dir`].
+/// The version string of the returned runtime is empty, call [`JavaRuntime::update`] to get it.
+///
+/// # Returns
+///
+/// * `Some(JavaRuntime)` if the given path is a `bin` directory containing the Java executable file.
+/// * `None` otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let root = env::temp_dir().join("java-runtimes-doctest-bin-dir-fast");
+/// # let bin = root.join("jdk-17/bin");
+/// # fs::create_dir_all(&bin).unwrap();
+/// let marker = root.join("executed");
+/// # let _ = fs::remove_file(&marker);
+/// // The fake java creates `marker` when executed
+/// let script = format!(
+///     "#!/bin/sh\ntouch '{}'\necho 'openjdk version \"17.0.4.1\"' >&2\n",
+///     marker.display()
+/// );
+/// # fs::write(bin.join("java"), script).unwrap();
+/// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+///
+/// let mut runtime = detector::detect_java_bin_dir_fast(&bin).unwrap();
+/// assert_eq!(runtime.get_version_string(), "");
+/// assert!(!marker.exists());
+///
+/// runtime.update().unwrap();
+/// assert_eq!(runtime.get_version_string(), "17.0.4.1");
+/// assert!(marker.exists());
+///
+/// assert!(detector::detect_java_bin_dir_fast(&root).is_none());
+/// # }
+/// ```
+pub fn detect_java_bin_dir_fast(bin_dir: &Path) -> Option<JavaRuntime> {
+    let executable = bin_dir.join(JavaRuntime::get_java_executable_name());
+    JavaRuntime::looks_like_java_executable_file(&executable)
+        .then(|| JavaRuntime::unprobed(&executable))
+}
+
+/// Attempts to detect a Java runtime from the given Java home directory path.
+///
+/// A macOS `.jdk` bundle path is also accepted, in which case the java home is `<bundle>/Contents/Home`.
+///
+/// # Returns
+///
+/// * `Some(JavaRuntime)` if the given path is a directory containing the `bin` subdirectory with the Java executable file.
+/// * `None` if the given path is not a directory containing the `bin` subdirectory with the Java executable file.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(unix)] {
+/// # use std::os::unix::fs::PermissionsExt;
+/// # use std::{env, fs};
+/// use java_runtimes::detector;
+///
+/// # let bundle = env::temp_dir().join("java-runtimes-doctest-bundle/temurin-17.jdk");
+/// # let bin = bundle.join("Contents/Home/bin");
+/// # fs::create_dir_all(&bin).unwrap();
+/// # let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+/// # fs::write(bin.join("java"), script).unwrap();
+/// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+/// // temurin-17.jdk/Contents/Home/bin/java
+/// let runtime = detector::detect_java_home_dir(&bundle).unwrap();
+/// assert_eq!(runtime.get_executable(), bundle.join("Contents/Home/bin/java"));
+///
+/// let runtime = detector::detect_java_home_dir(&bundle.join("Contents/Home")).unwrap();
+/// assert_eq!(runtime.get_version_string(), "17.0.4.1");
+/// # }
+/// ```
+pub fn detect_java_home_dir(java_home: &Path) -> Option<JavaRuntime> {
+    detect_java_home_dir_checked(java_home)?.ok()
+}
+
+/// Attempts to detect a Java runtime from the given java home, see [`detect_java_home_dir`]
+///
+/// Returns `None` if there is no java executable file in it.
+fn detect_java_home_dir_checked(java_home: &Path) -> Option<Result<JavaRuntime, Error>> {
+    let bundle_home = java_home.join("Contents").join("Home");
+    let home = if bundle_home.is_dir() {
+        bundle_home
+    } else {
+        java_home.to_path_buf()
+    };
+    let executable = home
+        .join("bin")
+        .join(JavaRuntime::get_java_executable_name());
+    executable
+        .is_file()
+        .then(|| JavaRuntime::from_executable(&executable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use crate::fixture::Fixture;
+    #[cfg(unix)]
+    use std::fs;
+
+    #[test]
+    fn registry_java_homes_reads_version_subkeys() {
+        let subkeys = [
+            ("1.8", Some(r"C:\Program Files\Java\jre1.8.0_301")),
+            ("1.8.0_301", Some(r"C:\Program Files\Java\jre1.8.0_301\")),
+            ("17", Some(r"C:\Program Files\Java\jdk-17")),
+            ("17.0.4.1", Some(r"c:\program files\java\JDK-17")),
+            ("21", None),
+            ("22", Some("  ")),
+            ("MSI", Some(r"C:\Program Files\Java\msi")),
+        ]
+        .map(|(version, java_home)| (version.to_string(), java_home.map(str::to_string)));
+
+        assert_eq!(
+            registry_java_homes(subkeys),
+            [
+                PathBuf::from(r"C:\Program Files\Java\jre1.8.0_301"),
+                PathBuf::from(r"C:\Program Files\Java\jdk-17"),
+            ]
+        );
+        assert!(registry_java_homes([]).is_empty());
+    }
+
+    #[test]
+    fn parse_java_home_plist_reads_home_paths() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<array>
+    <dict>
+        <key>JVMArch</key>
+        <string>arm64</string>
+        <key>JVMHomePath</key>
+        <string>/Library/Java/JavaVirtualMachines/temurin-21.jdk/Contents/Home</string>
+        <key>JVMName</key>
+        <string>OpenJDK 21.0.1</string>
+        <key>JVMVersion</key>
+        <string>21.0.1</string>
+    </dict>
+    <dict>
+        <key>JVMHomePath</key>
+        <string>/Users/me/Library/Java/JavaVirtualMachines/R&amp;D 17.jdk/Contents/Home</string>
+        <key>JVMVersion</key>
+        <string>17.0.4.1</string>
+    </dict>
+</array>
+</plist>
+"#;
+        assert_eq!(
+            parse_java_home_plist(plist),
+            [
+                PathBuf::from("/Library/Java/JavaVirtualMachines/temurin-21.jdk/Contents/Home"),
+                PathBuf::from(
+                    "/Users/me/Library/Java/JavaVirtualMachines/R&D 17.jdk/Contents/Home"
+                ),
+            ]
+        );
+        assert!(parse_java_home_plist("").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn best_jre_prefers_jre_over_jdk() {
+        let mut fixture = Fixture::new();
+        let jdk = fixture.java("jdk-99", "99.0.1");
+        fs::write(jdk.join("bin/javac"), "").unwrap();
+        let jre = fixture.java("jre-99", "99.0.1");
+        fixture.isolate_env();
+        fixture.set_var("JAVA_HOME", &jdk);
+        fixture.set_var("JRE_HOME", &jre);
+
+        let runtime = best_jre(99).unwrap();
+        assert_eq!(runtime.get_executable(), jre.join("bin/java"));
+        assert!(best_jre(100).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_where_returns_the_first_match() {
+        let mut fixture = Fixture::new();
+        fixture.isolate_env();
+        fixture.set_var("JAVA_HOME", fixture.java("jdk-11", "11.0.2"));
+        fixture.set_var("JDK_HOME", fixture.java("jdk-17", "17.0.4.1"));
+
+        let runtime = find_where(|r| r.get_version_string().starts_with("17.")).unwrap();
+        assert_eq!(runtime.get_version_string(), "17.0.4.1");
+        assert!(find_where(|r| r.get_version_string().starts_with("21.")).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_with_override_puts_the_custom_runtime_first() {
+        let mut fixture = Fixture::new();
+        let custom = fixture.java("jdk-21", "21.0.1");
+        fixture.isolate_env();
+        fixture.set_var("JAVA_HOME", fixture.java("jdk-17", "17.0.4.1"));
+        fixture.set_var("JDK_HOME", &custom);
+
+        let runtimes = detect_with_override(Some(&custom));
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes[0].get_version_string(), "21.0.1");
+        assert_eq!(runtimes[1].get_version_string(), "17.0.4.1");
+
+        let runtimes = detect_with_override(None);
+        assert_eq!(runtimes[0].get_version_string(), "17.0.4.1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_from_delimited_skips_invalid_homes() {
+        let fixture = Fixture::new();
+        let homes = std::env::join_paths([
+            fixture.java("jdk-17", "17.0.4.1"),
+            fixture.root().join("missing"),
+            fixture.java("jdk-21", "21.0.1"),
+        ])
+        .unwrap();
+
+        let runtimes = detect_from_delimited(homes.to_str().unwrap());
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes[0].get_version_string(), "17.0.4.1");
+        assert_eq!(runtimes[1].get_version_string(), "21.0.1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_java_with_default_depth_finds_direct_homes() {
+        let fixture = Fixture::new();
+        let root = fixture.root();
+        // root/jdk-17/bin/java
+        // root/vendor/jdk-17/bin/java
+        fixture.java("jdk-17", "17.0.4.1");
+        fixture.java("vendor/jdk-17", "17.0.4.1");
+
+        assert_eq!(DEFAULT_MAX_DEPTH, 2);
+        let runtimes = detect_java_with_default_depth(root);
+        assert_eq!(runtimes, detect_java(root, DEFAULT_MAX_DEPTH));
+        assert_eq!(runtimes.len(), 1);
+        assert_eq!(runtimes[0].get_executable(), root.join("jdk-17/bin/java"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_java_in_paths_parallel_by_root_keeps_path_order() {
+        let fixture = Fixture::new();
+        let root = fixture.root();
+        fixture.java("opt/jdk-17", "17.0.4.1");
+        fixture.java("nfs/jdk-21", "21.0.1");
+        let opt = root.join("opt");
+        let nfs = root.join("nfs");
+
+        // The same runtime is found through both `root` and `opt`
+        let runtimes = detect_java_in_paths_parallel_by_root(&[&opt, &nfs, root], 3);
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes[0].get_version_string(), "17.0.4.1");
+        assert_eq!(runtimes[1].get_version_string(), "21.0.1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_jar_runs_the_best_runtime() {
+        let mut fixture = Fixture::new();
+        let script = r#"#!/bin/sh
+case "$*" in
+    *-version) echo 'openjdk version "99.0.1" 2023-10-17' >&2 ;;
+    "-jar app.jar hello") exit 3 ;;
+    *) exit 1 ;;
+esac
+"#;
+        let home = fixture.java_script("jdk-99", script);
+        fixture.isolate_env();
+        fixture.set_var("JAVA_HOME", &home);
+        fixture.set_var("JDK_HOME", &home);
+
+        let status = run_jar("app.jar".as_ref(), 99, &["hello"]).unwrap();
+        assert_eq!(status.code(), Some(3));
+
+        let err = run_jar("app.jar".as_ref(), 100, &["hello"]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn diagnose_environment_reports_conflicts_and_invalid_homes() {
+        let mut fixture = Fixture::new();
+        let jdk8 = fixture.java("jdk-8", "1.8.0_333");
+        let jdk17 = fixture.java("jdk-17", "17.0.4.1");
+        fixture.set_var("PATH", jdk17.join("bin"));
+
+        fixture.set_var("JAVA_HOME", &jdk17);
+        assert!(diagnose_environment().is_empty());
+
+        fixture.set_var("JAVA_HOME", &jdk8);
+        let diagnostics = diagnose_environment();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(matches!(
+            diagnostics[0],
+            Diagnostic::JavaHomeConflictsWithPath { .. }
+        ));
+        assert!(diagnostics[0]
+            .to_string()
+            .starts_with("JAVA_HOME is Java 8 but `java` is Java 17"));
+
+        let missing = fixture.root().join("missing");
+        fixture.set_var("JAVA_HOME", &missing);
+        assert_eq!(
+            diagnose_environment(),
+            vec![Diagnostic::InvalidJavaHome(missing)]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn available_majors_collects_each_major_once() {
+        let mut fixture = Fixture::new();
+        fixture.isolate_env();
+        fixture.set_var("JAVA_HOME", fixture.java("jdk-17", "17.0.4.1"));
+        fixture.set_var("JDK_HOME", fixture.java("jdk-8", "1.8.0_333"));
+        fixture.set_var("JRE_HOME", fixture.java("jre-17", "17.0.8"));
+        fixture.set_var("PATH", fixture.java("jdk-21", "21.0.1").join("bin"));
+
+        assert!(available_majors().is_superset(&BTreeSet::from([8, 17, 21])));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_java_in_environments_splits_path() {
+        let mut fixture = Fixture::new();
+        let root = fixture.root().to_path_buf();
+        fixture.isolate_env();
+        // PATH=<root>/jdk-17/bin:<root>/usr/bin:<root>/jdk-21/bin
+        let path = std::env::join_paths([
+            fixture.java("jdk-17", "17.0.4.1").join("bin"),
+            root.join("usr/bin"),
+            fixture.java("jdk-21", "21.0.1").join("bin"),
+        ])
+        .unwrap();
+        fixture.set_var("PATH", path);
+
+        let runtimes = detect_java_in_environments();
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes[0].get_version_string(), "17.0.4.1");
+        assert_eq!(runtimes[1].get_version_string(), "21.0.1");
+
+        // JAVA_HOME and PATH often point to the same installation, maybe through a symlink
+        std::os::unix::fs::symlink(root.join("jdk-17"), root.join("current")).unwrap();
+        fixture.set_var("JAVA_HOME", root.join("jdk-17"));
+        fixture.set_var("PATH", root.join("current/bin"));
+        assert_eq!(detect_java_in_environments().len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_java_from_gradle_properties_prefers_user_level() {
+        let mut fixture = Fixture::new();
+        let root = fixture.root().to_path_buf();
+        let home = fixture.java("jdk-17", "17.0.4.1");
+        let user_home = root.join("gradle-user-home");
+        let project = root.join("project");
+        fs::create_dir_all(&user_home).unwrap();
+        fs::create_dir_all(&project).unwrap();
+        fixture.set_var("GRADLE_USER_HOME", &user_home);
+
+        let properties = format!(
+            "# Build settings\norg.gradle.jvmargs=-Xmx2g\norg.gradle.java.home = {}\n",
+            home.display()
+        );
+        fs::write(project.join("gradle.properties"), properties).unwrap();
+        let runtime = detect_java_from_gradle_properties(&project).unwrap();
+        assert_eq!(runtime.get_executable(), home.join("bin/java"));
+
+        // The user-level one takes precedence
+        let properties = "org.gradle.java.home=/missing/jdk\n";
+        fs::write(user_home.join("gradle.properties"), properties).unwrap();
+        assert!(detect_java_from_gradle_properties(&project).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_all_with_errors_reports_broken_runtimes() {
+        let mut fixture = Fixture::new();
+        let root = fixture.root().to_path_buf();
+        fixture.isolate_env();
+        fixture.set_var("JAVA_HOME", fixture.java("jdk-17", "17.0.4.1"));
+        // Points to nothing
+        fixture.set_var("JDK_HOME", root.join("jdk-removed"));
+        // A truncated download
+        fixture.set_var("JRE_HOME", fixture.java_script("jre-broken", ""));
+
+        let (runtimes, errors) = detect_all_with_errors();
+        let runtimes = runtimes
+            .iter()
+            .filter(|runtime| runtime.get_executable().starts_with(&root))
+            .collect::<Vec<_>>();
+        assert_eq!(runtimes.len(), 1);
+        assert_eq!(runtimes[0].get_version_string(), "17.0.4.1");
+
+        assert!(errors.iter().any(|err| matches!(
+            err.kind(),
+            ErrorKind::InvalidEnvironmentVariable { name, .. } if name == "JDK_HOME"
+        )));
+        assert!(errors.iter().any(|err| matches!(
+            err.kind(),
+            ErrorKind::CorruptExecutable(path) if path.starts_with(&root)
+        )));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_java_in_range_filters_major_versions() {
+        let fixture = Fixture::new();
+        fixture.java("jdk-8", "1.8.0_333");
+        fixture.java("jdk-11", "11.0.2");
+        fixture.java("jdk-17", "17.0.4.1");
+        fixture.java("jdk-21", "21.0.1");
+
+        let mut runtimes = detect_java_in_range(&[fixture.root()], 2, 11..=17);
+        runtimes.sort_by_key(JavaRuntime::sort_key);
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes[0].get_version_string(), "11.0.2");
+        assert_eq!(runtimes[1].get_version_string(), "17.0.4.1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_best_selects_the_newest() {
+        let fixture = Fixture::new();
+        fixture.java("jdk-8", "1.8.0_333");
+        fixture.java("jdk-17.0.8", "17.0.8");
+        fixture.java("jdk-17.0.10", "17.0.10");
+        fixture.java("jdk-11", "11.0.2");
+
+        let best = find_best(&[fixture.root()], 2).unwrap();
+        assert_eq!(best.get_version_string(), "17.0.10");
+        assert!(find_best(&[&fixture.root().join("missing")], 2).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_java_in_sdkman_skips_current() {
+        let mut fixture = Fixture::new();
+        let sdkman = fixture.root().join("sdkman");
+        fixture.java("sdkman/candidates/java/17.0.4.1-tem", "17.0.4.1");
+        fixture.java("sdkman/candidates/java/21.0.1-tem", "21.0.1");
+        // candidates/java/current -> 21.0.1-tem
+        let candidates = sdkman.join("candidates/java");
+        std::os::unix::fs::symlink(candidates.join("21.0.1-tem"), candidates.join("current"))
+            .unwrap();
+        fixture.set_var("SDKMAN_DIR", &sdkman);
+
+        let mut runtimes = detect_java_in_sdkman();
+        runtimes.sort_by_key(JavaRuntime::sort_key);
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes[0].get_version_string(), "17.0.4.1");
+        assert_eq!(runtimes[1].get_version_string(), "21.0.1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_java_in_asdf_skips_shims() {
+        let mut fixture = Fixture::new();
+        let asdf = fixture.root().join("asdf");
+        fixture.java("asdf/installs/java/temurin-17.0.4+101", "17.0.4");
+        let zulu = fixture.java("asdf/installs/java/zulu-21.30.15", "21.0.1");
+        fs::create_dir_all(asdf.join("shims")).unwrap();
+        fs::copy(zulu.join("bin/java"), asdf.join("shims/java")).unwrap();
+        fixture.set_var("ASDF_DATA_DIR", &asdf);
+
+        let mut runtimes = detect_java_in_asdf();
+        runtimes.sort_by_key(JavaRuntime::sort_key);
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes[0].get_version_string(), "17.0.4");
+        assert_eq!(runtimes[1].get_version_string(), "21.0.1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_all_combines_every_strategy() {
+        let mut fixture = Fixture::new();
+        let root = fixture.root().to_path_buf();
+        fixture.isolate_env();
+        let home = fixture.java("jdk-17", "17.0.4.1");
+        fixture.set_var("JAVA_HOME", &home);
+        fixture.set_var("JDK_HOME", &home);
+        fixture.java("sdkman/candidates/java/21.0.1-tem", "21.0.1");
+        fixture.set_var("SDKMAN_DIR", root.join("sdkman"));
+        fixture.java("asdf/installs/java/zulu-11.52.13", "11.0.14");
+        fixture.set_var("ASDF_DATA_DIR", root.join("asdf"));
+        let gradle = fixture.java("gradle-jdk", "1.8.0_333");
+        fs::create_dir_all(root.join("gradle")).unwrap();
+        let properties = format!("org.gradle.java.home={}\n", gradle.display());
+        fs::write(root.join("gradle/gradle.properties"), properties).unwrap();
+        fixture.set_var("GRADLE_USER_HOME", root.join("gradle"));
+
+        let versions = detect_all()
+            .iter()
+            .filter(|runtime| runtime.get_executable().starts_with(&root))
+            .map(|runtime| runtime.get_version_string().to_string())
+            .collect::<Vec<String>>();
+        // Sorted from the newest, and `JAVA_HOME` and `JDK_HOME` are reported once
+        assert_eq!(versions, ["21.0.1", "17.0.4.1", "11.0.14", "1.8.0_333"]);
+    }
 }
@@ -10,15 +10,72 @@ impl Error {
     pub(crate) fn new(kind: ErrorKind) -> Self {
         Error { kind }
     }
+
+    /// Get the kind of this error
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Check if this error is caused by lacking permission to execute java
+    pub(crate) fn is_permission_denied(&self) -> bool {
+        matches!(&self.kind, ErrorKind::JavaOutputFailed(err) if err.kind() == std::io::ErrorKind::PermissionDenied)
+    }
 }
 
+/// Enum [`ErrorKind`] represents the kinds of [`Error`].
+///
+/// New kinds may be added, and some only exist with certain features like `serde`,
+/// so a `match` on it needs a wildcard arm.
 #[derive(Debug)]
-pub(crate) enum ErrorKind {
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The current working directory is invalid
     InvalidWorkDir,
+    /// The given text does not contain a java version
     NoJavaVersionStringFound,
+    /// The path is not like `**/bin/java(.exe)` or `**/bin/javaw(.exe)`
     LooksNotLikeJavaExecutableFile(PathBuf),
+    /// Failed to execute the java executable file
     JavaOutputFailed(std::io::Error),
+    /// The java executable file exited with a failure status
     GettingJavaVersionFailed(PathBuf),
+    /// The java executable file succeeded but printed nothing
+    EmptyVersionOutput(PathBuf),
+    /// The java executable file is empty or not an executable format, e.g. a partially downloaded file
+    CorruptExecutable(PathBuf),
+    /// Failed to read the metadata of the java executable file
+    ReadingMetadataFailed(std::io::Error),
+    /// Java refused to start with the given options
+    RejectedJvmOption {
+        /// The option that caused the failure, if it's identified from the error output
+        option: Option<String>,
+        /// The first line of the error output, like `Unrecognized option: --foo`
+        message: String,
+    },
+    /// Failed to read a directory while searching for java runtimes
+    ReadingDirFailed(std::io::Error),
+    /// The `release` file in java home does not exist or can not be read
+    ReleaseFileNotFound(PathBuf),
+    /// Failed to serialize java runtimes
+    #[cfg(feature = "serde")]
+    Serialization(serde_json::Error),
+    /// An environment variable like `JAVA_HOME` points to a path that does not exist
+    InvalidEnvironmentVariable {
+        /// Name of the environment variable
+        name: String,
+        /// The path it points to
+        path: PathBuf,
+    },
+    /// The given path to search for java runtimes does not exist
+    PathNotFound(PathBuf),
+    /// Failed to resolve the canonical path of the java executable file
+    CanonicalizingFailed(std::io::Error),
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error::new(kind)
+    }
 }
 
 impl Display for Error {
@@ -29,7 +86,7 @@ impl Display for Error {
             ErrorKind::LooksNotLikeJavaExecutableFile(path) => {
                 write!(
                     f,
-                    "Path looks not like a Java executable file [**/bin/java(.exe)] : {}",
+                    "Path looks not like a Java executable file [**/bin/java(w)(.exe)] : {}",
                     path.display()
                 )
             }
@@ -39,6 +96,86 @@ impl Display for Error {
             ErrorKind::GettingJavaVersionFailed(path) => {
                 write!(f, "Failed to get Java version: {}", path.display())
             }
+            ErrorKind::EmptyVersionOutput(path) => {
+                write!(f, "Java printed no version output: {}", path.display())
+            }
+            ErrorKind::CorruptExecutable(path) => {
+                write!(
+                    f,
+                    "Java executable file is empty or corrupt, it may be truncated: {}",
+                    path.display()
+                )
+            }
+            ErrorKind::ReadingMetadataFailed(io_err) => {
+                write!(
+                    f,
+                    "Failed to read metadata of Java executable file: {}",
+                    io_err
+                )
+            }
+            ErrorKind::RejectedJvmOption {
+                option: Some(option),
+                message,
+            } => {
+                write!(f, "Java rejected option {}: {}", option, message)
+            }
+            ErrorKind::RejectedJvmOption {
+                option: None,
+                message,
+            } => {
+                write!(f, "Java rejected the options: {}", message)
+            }
+            ErrorKind::ReadingDirFailed(io_err) => {
+                write!(f, "Failed to read directory: {}", io_err)
+            }
+            ErrorKind::ReleaseFileNotFound(path) => {
+                write!(f, "Release file not found: {}", path.display())
+            }
+            #[cfg(feature = "serde")]
+            ErrorKind::Serialization(serde_err) => {
+                write!(f, "Failed to serialize Java runtimes: {}", serde_err)
+            }
+            ErrorKind::InvalidEnvironmentVariable { name, path } => {
+                write!(f, "{} points to a missing path: {}", name, path.display())
+            }
+            ErrorKind::PathNotFound(path) => {
+                write!(f, "Path not found: {}", path.display())
+            }
+            ErrorKind::CanonicalizingFailed(io_err) => {
+                write!(f, "Failed to canonicalize Java executable path: {}", io_err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    /// Get the underlying io error of [`ErrorKind::JavaOutputFailed`], [`ErrorKind::ReadingMetadataFailed`], [`ErrorKind::ReadingDirFailed`]
+    /// and [`ErrorKind::CanonicalizingFailed`],
+    /// or the serde error of `ErrorKind::Serialization`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::error::{Error, ErrorKind};
+    /// use std::io;
+    ///
+    /// let io_err = io::Error::new(io::ErrorKind::PermissionDenied, "permission denied");
+    /// let err: Box<dyn std::error::Error> = Box::new(Error::from(ErrorKind::JavaOutputFailed(io_err)));
+    /// assert!(err.source().is_some());
+    /// assert_eq!(err.source().unwrap().to_string(), "permission denied");
+    ///
+    /// let err: Box<dyn std::error::Error> = Box::new(Error::from(ErrorKind::NoJavaVersionStringFound));
+    /// assert!(err.source().is_none());
+    /// ```
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::JavaOutputFailed(io_err)
+            | ErrorKind::ReadingMetadataFailed(io_err)
+            | ErrorKind::ReadingDirFailed(io_err)
+            | ErrorKind::CanonicalizingFailed(io_err) => Some(io_err),
+            #[cfg(feature = "serde")]
+            ErrorKind::Serialization(serde_err) => Some(serde_err),
+            _ => None,
         }
     }
 }
@@ -0,0 +1,103 @@
+//! Fake java homes and environment variables shared by the unit tests
+
+use std::ffi::{OsStr, OsString};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::{env, fs, process};
+
+/// Held by the fixture of the running test
+///
+/// Tests write executable files and change environment variables, which are shared by the whole process,
+/// so they run one at a time.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// A unique temporary directory to create fake java homes in
+///
+/// The directory is removed and the changed environment variables are restored when it's dropped.
+pub(crate) struct Fixture {
+    root: PathBuf,
+    saved_vars: Vec<(OsString, Option<OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Fixture {
+    pub(crate) fn new() -> Fixture {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let lock = LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let root = env::temp_dir().join(format!(
+            "java-runtimes-test-{}-{}",
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        Fixture {
+            root,
+            saved_vars: vec![],
+            _lock: lock,
+        }
+    }
+
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Creates `<root>/<home>/bin/java` printing the given version like `java -version`, and returns the java home
+    pub(crate) fn java(&self, home: &str, version: &str) -> PathBuf {
+        let script = format!("#!/bin/sh\necho 'openjdk version \"{}\"' >&2\n", version);
+        self.java_script(home, &script)
+    }
+
+    /// Creates `<root>/<home>/bin/java` running the given shell script, and returns the java home
+    pub(crate) fn java_script(&self, home: &str, script: &str) -> PathBuf {
+        let home = self.root.join(home);
+        let executable = home.join("bin").join("java");
+        fs::create_dir_all(home.join("bin")).unwrap();
+        fs::write(&executable, script).unwrap();
+        fs::set_permissions(&executable, fs::Permissions::from_mode(0o755)).unwrap();
+        home
+    }
+
+    /// Sets an environment variable until the fixture is dropped
+    pub(crate) fn set_var(&mut self, key: &str, value: impl AsRef<OsStr>) {
+        self.save_var(key);
+        env::set_var(key, value);
+    }
+
+    /// Removes an environment variable until the fixture is dropped
+    pub(crate) fn remove_var(&mut self, key: &str) {
+        self.save_var(key);
+        env::remove_var(key);
+    }
+
+    /// Points the environment variables searched for java homes, and the directories of tools to nothing
+    pub(crate) fn isolate_env(&mut self) {
+        for key in ["JAVA_HOME", "JAVA_ROOT", "JDK_HOME", "JRE_HOME"] {
+            self.remove_var(key);
+        }
+        self.set_var("PATH", self.root.join("missing"));
+        for key in ["SDKMAN_DIR", "ASDF_DATA_DIR", "GRADLE_USER_HOME"] {
+            self.set_var(key, self.root.join("missing"));
+        }
+    }
+
+    fn save_var(&mut self, key: &str) {
+        if !self.saved_vars.iter().any(|(saved, _)| saved == key) {
+            self.saved_vars.push((key.into(), env::var_os(key)));
+        }
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        for (key, value) in self.saved_vars.drain(..).rev() {
+            match value {
+                Some(value) => env::set_var(key, value),
+                None => env::remove_var(key),
+            }
+        }
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
@@ -1,6 +1,17 @@
 //! `java-runtimes` is a rust library for detecting java runtimes in current system.
 //!
 //! * To detect java runtimes, see [`detector`]
+//! * To compare detection results over time, see [`report`]
+//! * To get version outputs without executing java, see [`prober`]
+//! * To tell distributions like Temurin or Corretto apart, see [`vendor`]
+//! * To get the runtime name, VM name and VM mode, see [`version_info`]
+//!
+//! # Features
+//!
+//! * `serde` (default): Serialize and deserialize [`JavaRuntime`] and [`report::DetectionReport`]
+//! * `registry` (default): Detect java runtimes from the Windows registry
+//! * `rayon`: Probe java executable files in parallel, see `detector::detect_java_parallel`
+//! * `semver`: Convert versions to `semver::Version`, see `JavaRuntime::to_semver`
 //!
 //! # Examples
 //!
@@ -28,29 +39,100 @@
 
 pub mod detector;
 pub mod error;
+#[cfg(all(test, unix))]
+mod fixture;
+pub mod prober;
+mod properties;
+mod release;
+pub mod report;
+pub mod vendor;
+pub mod version_info;
 
 use crate::error::{Error, ErrorKind};
+use crate::prober::{CommandProber, VersionProber};
+use crate::vendor::Vendor;
+use crate::version_info::VersionInfo;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
 use std::env;
 use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Struct [`JavaRuntime`] Represents a java runtime in specific path.
 ///
 /// To detect java runtimes from specific path, see [`detector`]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub struct JavaRuntime {
     os: String,
     path: PathBuf,
     version_string: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    vendor: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    arch: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    version_info: Option<VersionInfo>,
+}
+
+/// Enum [`LibcKind`] represents the C library a java runtime was built against.
+///
+/// See [`JavaRuntime::libc_kind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibcKind {
+    /// GNU C library, used by most Linux distributions
+    Glibc,
+    /// musl, used by Alpine Linux
+    Musl,
+    /// Other C libraries
+    Unknown,
+}
+
+/// Struct [`ExecutableMeta`] holds the size and permissions of a java executable file.
+///
+/// See [`JavaRuntime::executable_metadata`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutableMeta {
+    size: u64,
+    mode: Option<u32>,
+}
+
+impl ExecutableMeta {
+    /// Get the size of the file in bytes
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    /// Get the permission bits of the file, like `0o755`
+    ///
+    /// Returns `None` on non-unix platforms.
+    pub fn get_mode(&self) -> Option<u32> {
+        self.mode
+    }
 }
 
 impl JavaRuntime {
     /// Used to match the version string in the command output
     ///
-    const VERSION_PATTERN: &'static str = r#".*"((\d+)\.(\d+)([\d._]+)?)".*"#;
+    /// The first quoted token after `version` like `version "17.0.4.1"` wins, or the whole string if it's a bare version.
+    /// A bare major version like `"21"` and pre-release or build suffixes like `"17-ea"` and `"21.0.1+12"` are accepted.
+    const VERSION_PATTERN: &'static str =
+        r#"(?:^"?|\bversion )"(\d+(?:[._]\d+)*(?:[-+][\w.+-]*)?)""#;
+    /// Used to match the unquoted version in the first line of `java --version` output, like `openjdk 17.0.4.1 2022-08-18`
+    const MODERN_VERSION_PATTERN: &'static str =
+        r"(?m)^\s*(?:openjdk|java) (\d+(?:[._]\d+)*(?:[-+][\w.+-]*)?)(?:\s|$)";
+    /// Used to match the version in the name of java home directory, like `17` in `jdk-17`
+    const DIR_VERSION_PATTERN: &'static str = r"\d+(?:[._]\d+)*";
+    /// Used to match the build string in the `Runtime Environment` line of the command output
+    const BUILD_PATTERN: &'static str = r"Runtime Environment[^\n]*\(build ([^,)\s]+)";
     /// Create a [`JavaRuntime`] object from the path of java executable file
     ///
     /// It executes command `java -version` to get the version information
@@ -68,15 +150,150 @@ impl JavaRuntime {
     /// let _ = JavaRuntime::from_executable(r"../../runtimes/jdk-1.8.0_291/bin/java".as_ref());
     /// ```
     pub fn from_executable(path: &Path) -> Result<Self, Error> {
-        let mut java = Self {
+        Self::from_executable_with_env(path, &[])
+    }
+
+    /// Create a [`JavaRuntime`] object from the path of java executable file, with extra environment variables
+    ///
+    /// Same as [`JavaRuntime::from_executable`], but the given environment variables are set
+    /// when executing `java -version`. It's useful for predictable output, e.g. `LANG=C`.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` Path to java executable file.
+    /// * `env` Environment variables as `(key, value)` pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let env = [("LANG".to_string(), "C".to_string())];
+    /// let _ = JavaRuntime::from_executable_with_env("/usr/lib/jvm/jdk-17/bin/java".as_ref(), &env);
+    /// ```
+    pub fn from_executable_with_env(path: &Path, env: &[(String, String)]) -> Result<Self, Error> {
+        Self::from_executable_with_prober(path, &CommandProber::with_env(env))
+    }
+
+    /// Create a [`JavaRuntime`] object from the path of java executable file, with the given prober
+    ///
+    /// The prober gets the version output instead of executing `java -version` directly,
+    /// see [`prober`] for examples.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` Path to java executable file.
+    /// * `prober` Used to get the version output of the java executable file.
+    pub fn from_executable_with_prober(
+        path: &Path,
+        prober: &dyn VersionProber,
+    ) -> Result<Self, Error> {
+        let mut java = Self::unprobed(path);
+        java.update_with_prober(prober)?;
+        Ok(java)
+    }
+
+    /// Create a [`JavaRuntime`] object of the current os with an empty version string
+    pub(crate) fn unprobed(path: &Path) -> Self {
+        Self {
             os: env::consts::OS.to_string(),
             path: path.to_path_buf(),
             version_string: String::new(),
-        };
-        java.update()?;
+            vendor: None,
+            arch: None,
+            version_info: None,
+        }
+    }
+
+    /// Create a [`JavaRuntime`] object from the path of java executable file, without executing it
+    ///
+    /// The version is read from the `JAVA_VERSION` entry of the `release` file in java home.
+    pub(crate) fn from_release(path: &Path) -> Result<Self, Error> {
+        let mut java = Self::unprobed(path);
+        let home = java
+            .get_home()
+            .ok_or(Error::new(ErrorKind::NoJavaVersionStringFound))?;
+        let from_release = Self::from_release_file(&home)?;
+        java.version_string = from_release.version_string;
+        java.vendor = from_release.vendor;
         Ok(java)
     }
 
+    /// Create a [`JavaRuntime`] object from the `release` file in java home, without executing java
+    ///
+    /// It's useful where spawning processes is not allowed.
+    /// The version is read from the `JAVA_VERSION` entry, and the vendor from the `IMPLEMENTOR` entry.
+    ///
+    /// # Parameters
+    ///
+    /// * `java_home` The java home directory, like `/usr/lib/jvm/java-17-openjdk`
+    ///
+    /// # Errors
+    ///
+    /// * [`ErrorKind::ReleaseFileNotFound`] if `<java_home>/release` can not be read
+    /// * [`ErrorKind::NoJavaVersionStringFound`] if it has no valid `JAVA_VERSION` entry
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::error::ErrorKind;
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// let root = env::temp_dir().join("java-runtimes-doctest-from-release-file");
+    /// let home = root.join("jdk-21");
+    /// fs::create_dir_all(&home).unwrap();
+    /// let release = r#"IMPLEMENTOR="Eclipse Adoptium"
+    /// IMPLEMENTOR_VERSION="Temurin-21.0.1+12"
+    /// JAVA_RUNTIME_VERSION="21.0.1+12-LTS"
+    /// JAVA_VERSION="21.0.1"
+    /// JAVA_VERSION_DATE="2023-10-17"
+    /// OS_ARCH="x86_64"
+    /// "#;
+    /// fs::write(home.join("release"), release).unwrap();
+    ///
+    /// let runtime = JavaRuntime::from_release_file(&home).unwrap();
+    /// assert_eq!(runtime.get_version_string(), "21.0.1");
+    /// assert_eq!(runtime.get_vendor(), Some("Eclipse Adoptium"));
+    /// assert_eq!(runtime.get_executable().parent().unwrap(), home.join("bin"));
+    ///
+    /// // The version of GA releases may have no dot
+    /// fs::write(home.join("release"), "JAVA_VERSION=\"21\"\n").unwrap();
+    /// let runtime = JavaRuntime::from_release_file(&home).unwrap();
+    /// assert_eq!(runtime.get_major_version(), 21);
+    /// assert_eq!(runtime.get_vendor(), None);
+    ///
+    /// fs::write(home.join("release"), "IMPLEMENTOR=\"Oracle Corporation\"\n").unwrap();
+    /// let err = JavaRuntime::from_release_file(&home).unwrap_err();
+    /// assert!(matches!(err.kind(), ErrorKind::NoJavaVersionStringFound));
+    ///
+    /// let err = JavaRuntime::from_release_file(&root.join("missing")).unwrap_err();
+    /// assert!(matches!(err.kind(), ErrorKind::ReleaseFileNotFound(_)));
+    /// ```
+    pub fn from_release_file(java_home: &Path) -> Result<Self, Error> {
+        let release_file = java_home.join("release");
+        let mut entries = release::read_release(&release_file)
+            .ok_or(Error::new(ErrorKind::ReleaseFileNotFound(release_file)))?;
+        let version_string = entries
+            .get("JAVA_VERSION")
+            .and_then(|version| {
+                Regex::new(&format!("^{}", Self::DIR_VERSION_PATTERN))
+                    .unwrap()
+                    .find(version)
+            })
+            .ok_or(Error::new(ErrorKind::NoJavaVersionStringFound))?
+            .as_str()
+            .to_string();
+        Ok(Self {
+            os: env::consts::OS.to_string(),
+            path: java_home.join("bin").join(Self::get_java_executable_name()),
+            version_string,
+            vendor: entries.remove("IMPLEMENTOR"),
+            arch: None,
+            version_info: None,
+        })
+    }
+
     /// Mannually create a [`JavaRuntime`] instance, without checking if it's available
     ///
     /// # Parameters
@@ -102,11 +319,16 @@ impl JavaRuntime {
     /// assert!(runtime.is_same_os());
     /// ```
     pub fn new(os: &str, path: &Path, version_string: &str) -> Result<Self, Error> {
+        let vendor = Self::extract_vendor(version_string);
+        let version_info = Self::parse_version_info(version_string);
         let version_string = Self::extract_version(version_string)?;
         Ok(Self {
             os: os.to_string(),
             path: path.to_path_buf(),
             version_string: version_string.to_string(),
+            vendor,
+            arch: None,
+            version_info,
         })
     }
 
@@ -131,211 +353,1950 @@ impl JavaRuntime {
         &self.path
     }
 
-    /// Returns `true` if the `Path` has a root.
+    /// Get the size and permissions of the java executable file
     ///
-    /// Refer to [`Path::has_root`]
+    /// It's a quick fingerprint for audit logs, without hashing the whole file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ErrorKind::ReadingMetadataFailed`] if failed to read the metadata, e.g. the file does not exist
     ///
     /// # Examples
     ///
     /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
     /// use java_runtimes::JavaRuntime;
     ///
-    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
-    /// assert!(runtime.has_root());
-    ///
-    /// let runtime = JavaRuntime::new("windows", r"D:\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
-    /// assert!(runtime.has_root());
+    /// # let bin = env::temp_dir().join("java-runtimes-doctest-executable-metadata/jdk-17/bin");
+    /// # fs::create_dir_all(&bin).unwrap();
+    /// let script = "#!/bin/sh\necho 'openjdk version \"17.0.4.1\" 2022-08-18' >&2\n";
+    /// fs::write(bin.join("java"), script).unwrap();
+    /// fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o750)).unwrap();
     ///
-    /// let runtime = JavaRuntime::new("linux", "../jdk/bin/java".as_ref(), "21.0.3").unwrap();
-    /// assert!(!runtime.has_root());
+    /// let runtime = JavaRuntime::from_executable(&bin.join("java")).unwrap();
+    /// let meta = runtime.executable_metadata().unwrap();
+    /// assert_eq!(meta.get_size(), script.len() as u64);
+    /// assert_eq!(meta.get_mode(), Some(0o750));
     ///
-    /// let runtime = JavaRuntime::new("windows", r"..\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
-    /// assert!(!runtime.has_root());
+    /// let missing = JavaRuntime::new("linux", "/missing/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert!(missing.executable_metadata().is_err());
+    /// # }
     /// ```
-    pub fn has_root(&self) -> bool {
-        self.path.has_root()
+    pub fn executable_metadata(&self) -> Result<ExecutableMeta, Error> {
+        let metadata = fs::metadata(&self.path)
+            .map_err(|err| Error::new(ErrorKind::ReadingMetadataFailed(err)))?;
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            Some(metadata.permissions().mode() & 0o7777)
+        };
+        #[cfg(not(unix))]
+        let mode = None;
+        Ok(ExecutableMeta {
+            size: metadata.len(),
+            mode,
+        })
     }
 
-    /// Get the version string
+    /// Get the java home directory, which contains the `bin` directory of the java executable file
+    ///
+    /// If the executable file exists, symlinks are resolved first. So for `/usr/bin/java` linking to
+    /// `/etc/alternatives/java` and then to `/usr/lib/jvm/jdk-17/bin/java`, the home is `/usr/lib/jvm/jdk-17`
+    /// rather than `/usr`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` if the executable file is inside a `bin` directory.
+    /// * `None` if the executable file is not inside a `bin` directory.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use java_runtimes::JavaRuntime;
+    /// use std::path::Path;
     ///
     /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
-    /// assert_eq!(runtime.get_version_string(), "21.0.3");
+    /// assert_eq!(runtime.get_home().unwrap(), Path::new("/jdk"));
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/java".as_ref(), "21.0.3").unwrap();
+    /// assert_eq!(runtime.get_home(), None);
     /// ```
-    pub fn get_version_string(&self) -> &str {
-        &self.version_string
-    }
-
-    /// Check if this is the same os as current
-    pub fn is_same_os(&self) -> bool {
-        self.os == env::consts::OS
-    }
-
-    /// Create a new [`JavaRuntime`] with absolute path.
     ///
-    /// # Errors
+    /// Windows paths are split by both `\` and `/`, and the `bin` directory name is case-insensitive,
+    /// even if the runtime is deserialized on another os.
     ///
-    /// Returns an [`Err`] if the current working directory value is invalid. Refer to [`env::current_dir`]
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::path::Path;
     ///
-    /// Possible cases:
+    /// let runtime = JavaRuntime::new("windows", r"D:\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
+    /// assert_eq!(runtime.get_home().unwrap(), Path::new(r"D:\jdk"));
     ///
-    /// * Current directory does not exist.
-    /// * There are insufficient permissions to access the current directory.
-    pub fn to_absolute(&self) -> Result<Self, Error> {
-        let cwd = env::current_dir().or(Err(Error::new(ErrorKind::InvalidWorkDir)))?;
-        let path_absolute = self.path.join(cwd);
-        let new_runtime = Self::new(&self.os, &path_absolute, &self.version_string)?;
-        Ok(new_runtime)
-    }
-
-    /// Try executing `java -version` and parse the output to get the version.
+    /// let runtime = JavaRuntime::new("windows", r"D:\jdk\BIN\java.exe".as_ref(), "21.0.3").unwrap();
+    /// assert_eq!(runtime.get_home().unwrap(), Path::new(r"D:\jdk"));
     ///
-    /// If success, it will update the version value in this [`JavaRuntime`] instance.
-    pub fn update(&mut self) -> Result<(), Error> {
-        if !Self::looks_like_java_executable_file(&self.path) {
-            return Err(Error::new(ErrorKind::LooksNotLikeJavaExecutableFile(
-                self.path.clone(),
-            )));
+    /// let runtime = JavaRuntime::new("windows", r"D:\jdk\java.exe".as_ref(), "21.0.3").unwrap();
+    /// assert_eq!(runtime.get_home(), None);
+    /// ```
+    ///
+    /// Symlink chain like `/usr/bin/java -> /etc/alternatives/java -> /usr/lib/jvm/jdk-17/bin/java`
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::symlink;
+    /// # use std::{env, fs};
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-get-home");
+    /// # let jdk = root.join("usr/lib/jvm/jdk-17");
+    /// # fs::create_dir_all(jdk.join("bin")).unwrap();
+    /// # fs::create_dir_all(root.join("usr/bin")).unwrap();
+    /// # fs::create_dir_all(root.join("etc/alternatives")).unwrap();
+    /// # fs::write(jdk.join("bin/java"), "").unwrap();
+    /// # let _ = symlink(jdk.join("bin/java"), root.join("etc/alternatives/java"));
+    /// # let _ = symlink(root.join("etc/alternatives/java"), root.join("usr/bin/java"));
+    /// let runtime = JavaRuntime::new("linux", &root.join("usr/bin/java"), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.get_home().unwrap(), jdk.canonicalize().unwrap());
+    /// # }
+    /// ```
+    ///
+    /// The verbatim prefix `\\?\` of canonical paths on Windows is removed, so the home can be used as `JAVA_HOME`
+    ///
+    /// ```rust
+    /// # #[cfg(windows)] {
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// let home = env::temp_dir().join("java-runtimes-doctest-get-home-windows").join("jdk-17");
+    /// fs::create_dir_all(home.join("bin")).unwrap();
+    /// fs::write(home.join("bin").join("java.exe"), "").unwrap();
+    ///
+    /// let runtime = JavaRuntime::new("windows", &home.join(r"bin\java.exe"), "17.0.4.1").unwrap();
+    /// let java_home = runtime.get_home().unwrap();
+    /// assert!(!java_home.to_string_lossy().starts_with(r"\\?\"));
+    /// assert_eq!(java_home.canonicalize().unwrap(), home.canonicalize().unwrap());
+    /// # }
+    /// ```
+    pub fn get_home(&self) -> Option<PathBuf> {
+        let path = self
+            .path
+            .canonicalize()
+            .map(strip_verbatim_prefix)
+            .unwrap_or_else(|_| self.path.clone());
+        if self.is_windows() {
+            let path = path.to_string_lossy();
+            let mut parts = path.rsplitn(3, ['\\', '/']);
+            let (_, bin, home) = (parts.next()?, parts.next()?, parts.next()?);
+            return (bin.eq_ignore_ascii_case("bin") && !home.is_empty())
+                .then(|| PathBuf::from(home));
         }
-
-        let output = Command::new(&self.path)
-            .arg("-version")
-            .output()
-            .map_err(|err| Error::new(ErrorKind::JavaOutputFailed(err)))?;
-
-        if output.status.success() {
-            let version_output = String::from_utf8_lossy(&output.stderr).to_string();
-            self.version_string = Self::extract_version(&version_output)?;
-            Ok(())
-        } else {
-            Err(Error::new(ErrorKind::GettingJavaVersionFailed(
-                self.path.clone(),
-            )))
+        let bin = path.parent()?;
+        if bin.file_name()? != "bin" {
+            return None;
         }
+        Some(bin.parent()?.to_path_buf())
     }
 
-    /// Test if this runtime is available currently
-    ///
-    /// It executes command `java -version` to see if it works
-    pub fn is_available(&self) -> bool {
-        self.is_same_os() && Self::from_executable(&self.path).is_ok()
-    }
-
-    /// Parse version string
+    /// Get the `jmods` directory in java home, which is needed by `jlink`
     ///
-    /// # Return
+    /// # Returns
     ///
-    /// `(version_string, version_major)`
+    /// * `Some(PathBuf)` if `<home>/jmods` exists (modular JDKs, Java 9+)
+    /// * `None` if it does not exist, like in Java 8 or JREs
     ///
     /// # Examples
     ///
     /// ```rust
     /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
     ///
-    /// assert_eq!(JavaRuntime::extract_version("1.8.0_333").unwrap(), "1.8.0_333");
-    /// assert_eq!(JavaRuntime::extract_version("17.0.4.1").unwrap(), "17.0.4.1");
-    /// assert_eq!(JavaRuntime::extract_version("\"17.0.4.1").unwrap(), "17.0.4.1");
-    /// assert_eq!(JavaRuntime::extract_version("java version \"17.0.4.1\"").unwrap(), "17.0.4.1");
+    /// let root = env::temp_dir().join("java-runtimes-doctest-jmods");
+    ///
+    /// let jdk17 = root.join("jdk-17");
+    /// fs::create_dir_all(jdk17.join("jmods")).unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk17.join("bin/java"), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.get_jmods_dir().unwrap(), jdk17.join("jmods"));
+    ///
+    /// let jdk8 = root.join("jdk-1.8");
+    /// fs::create_dir_all(jdk8.join("jre")).unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk8.join("bin/java"), "1.8.0_333").unwrap();
+    /// assert_eq!(runtime.get_jmods_dir(), None);
     /// ```
-    pub fn extract_version(version_string: &str) -> Result<String, Error> {
-        Ok(Regex::new(Self::VERSION_PATTERN)
-            .unwrap()
-            .captures(&format!("\"{}\"", &version_string))
-            .ok_or(Error::new(ErrorKind::NoJavaVersionStringFound))?
-            .get(1)
-            .ok_or(Error::new(ErrorKind::NoJavaVersionStringFound))?
-            .as_str()
-            .to_string())
+    pub fn get_jmods_dir(&self) -> Option<PathBuf> {
+        let jmods = self.get_home()?.join("jmods");
+        jmods.is_dir().then_some(jmods)
     }
 
-    /// Check if the given path looks like a java executable file
+    /// Get the C library this runtime was built against
     ///
-    /// The file must exists.
+    /// It reads the `LIBC` entry of the `release` file in java home, which is published by some vendors like Temurin.
+    /// If there is no such entry, on Linux the dynamic linker referenced by the java executable file is inspected.
     ///
-    /// The given path must be `**/bin/java.exe` in windows, or `**/bin/java` in unix
-    fn looks_like_java_executable_file(path: &Path) -> bool {
-        if !path.is_file() {
-            return false;
+    /// # Returns
+    ///
+    /// * `Some(LibcKind)` if the C library is known, or [`LibcKind::Unknown`] for unrecognized `LIBC` values
+    /// * `None` if no information found
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::{JavaRuntime, LibcKind};
+    /// use std::{env, fs};
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-libc");
+    /// # let runtime = |home: &str, release: &str| {
+    /// #     fs::create_dir_all(root.join(home)).unwrap();
+    /// #     fs::write(root.join(home).join("release"), release).unwrap();
+    /// #     JavaRuntime::new("linux", &root.join(home).join("bin/java"), "17.0.4.1").unwrap()
+    /// # };
+    /// let alpine = runtime("jdk-17-alpine", "LIBC=\"musl\"\n");
+    /// assert_eq!(alpine.libc_kind(), Some(LibcKind::Musl));
+    ///
+    /// let debian = runtime("jdk-17-debian", "LIBC=\"gnu\"\n");
+    /// assert_eq!(debian.libc_kind(), Some(LibcKind::Glibc));
+    ///
+    /// let other = runtime("jdk-17-other", "LIBC=\"bionic\"\n");
+    /// assert_eq!(other.libc_kind(), Some(LibcKind::Unknown));
+    /// ```
+    pub fn libc_kind(&self) -> Option<LibcKind> {
+        if let Some(libc) = self.release_entry("LIBC") {
+            return Some(match libc.as_str() {
+                "gnu" | "glibc" => LibcKind::Glibc,
+                "musl" => LibcKind::Musl,
+                _ => LibcKind::Unknown,
+            });
         }
-        // to absolute
-        let path_absolute = match path.canonicalize() {
-            Ok(path) => path,
-            _ => return false,
-        };
-        // check file name
-        if let Some(file_name) = path_absolute.file_name() {
-            if file_name == Self::get_java_executable_name() {
-                // check parent name
-                if let Some(parent) = path_absolute.parent() {
-                    if let Some(dir_name) = parent.file_name() {
-                        if dir_name == "bin" {
-                            return true;
-                        }
-                    }
-                }
+        if cfg!(target_os = "linux") {
+            let content = fs::read(&self.path).ok()?;
+            let contains = |needle: &[u8]| content.windows(needle.len()).any(|w| w == needle);
+            if contains(b"ld-musl") {
+                return Some(LibcKind::Musl);
+            } else if contains(b"ld-linux") {
+                return Some(LibcKind::Glibc);
             }
         }
-        false
+        None
     }
 
+    /// Get the path of the `release` file in java home
+    ///
+    /// The `release` file describes the runtime with lines like `JAVA_VERSION="17.0.4.1"`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` if `<home>/release` exists
+    /// * `None` if it does not exist
+    ///
     /// # Examples
-    /// * `java.exe` (windows)
-    /// * `java` (linux)
-    fn get_java_executable_name() -> OsString {
-        let mut java_exe = OsString::from("java");
-        java_exe.push(env::consts::EXE_SUFFIX);
-        java_exe
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// let root = env::temp_dir().join("java-runtimes-doctest-release-path");
+    ///
+    /// let jdk = root.join("jdk-17");
+    /// fs::create_dir_all(&jdk).unwrap();
+    /// fs::write(jdk.join("release"), "JAVA_VERSION=\"17.0.4.1\"\n").unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk.join("bin/java"), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.release_file_path().unwrap(), jdk.join("release"));
+    ///
+    /// let runtime = JavaRuntime::new("linux", &root.join("jre-8/bin/java"), "1.8.0_333").unwrap();
+    /// assert_eq!(runtime.release_file_path(), None);
+    /// ```
+    pub fn release_file_path(&self) -> Option<PathBuf> {
+        let release = self.get_home()?.join("release");
+        release.is_file().then_some(release)
     }
-}
-impl Clone for JavaRuntime {
+
+    /// Read an entry of the `release` file in java home
+    fn release_entry(&self, key: &str) -> Option<String> {
+        release::read_release(&self.release_file_path()?)?.remove(key)
+    }
+
+    /// Check if the sources of the java class library are shipped with this runtime
+    ///
+    /// It looks for `lib/src.zip` in java home, or `src.zip` for Java 8 and earlier.
+    ///
     /// # Examples
     ///
     /// ```rust
     /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
     ///
-    /// let r1 = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
-    /// let r2 = r1.clone();
+    /// let root = env::temp_dir().join("java-runtimes-doctest-sources");
     ///
-    /// assert_eq!(r1, r2);
+    /// let jdk = root.join("jdk-17");
+    /// fs::create_dir_all(jdk.join("lib")).unwrap();
+    /// fs::write(jdk.join("lib/src.zip"), "").unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk.join("bin/java"), "17.0.4.1").unwrap();
+    /// assert!(runtime.has_sources());
+    ///
+    /// let jre = root.join("jre-17");
+    /// fs::create_dir_all(jre.join("lib")).unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jre.join("bin/java"), "17.0.4.1").unwrap();
+    /// assert!(!runtime.has_sources());
     /// ```
-    fn clone(&self) -> Self {
-        Self {
-            os: self.os.clone(),
-            path: self.path.clone(),
-            version_string: self.version_string.clone(),
+    pub fn has_sources(&self) -> bool {
+        match self.get_home() {
+            Some(home) => {
+                home.join("lib").join("src.zip").is_file() || home.join("src.zip").is_file()
+            }
+            None => false,
         }
     }
+
+    /// Test if files can be created in java home, e.g. before installing a policy file into it
+    ///
+    /// It creates and then removes a probe file in java home.
+    /// Returns `false` if java home can not be derived, does not exist, or is read-only.
+    ///
     /// # Examples
     ///
     /// ```rust
     /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
     ///
-    /// let mut r1 = JavaRuntime::new("windows", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
-    /// let r2 = JavaRuntime::new("windows", r"D:\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
+    /// let home = env::temp_dir().join("java-runtimes-doctest-writable/jdk-17");
+    /// fs::create_dir_all(&home).unwrap();
+    /// let runtime = JavaRuntime::new("linux", &home.join("bin/java"), "17.0.4.1").unwrap();
+    /// assert!(runtime.home_is_writable());
     ///
-    /// r1.clone_from(&r2);
-    /// assert_eq!(r1, r2);
+    /// let missing = home.with_file_name("missing");
+    /// let runtime = JavaRuntime::new("linux", &missing.join("bin/java"), "17.0.4.1").unwrap();
+    /// assert!(!runtime.home_is_writable());
     /// ```
-    fn clone_from(&mut self, source: &Self) {
-        self.os = source.os.clone();
-        self.path = source.path.clone();
-        self.version_string = source.version_string.clone();
+    pub fn home_is_writable(&self) -> bool {
+        let home = match self.get_home() {
+            Some(home) => home,
+            None => return false,
+        };
+        let probe = home.join(format!(".java-runtimes-probe-{}", std::process::id()));
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&probe)
+        {
+            Ok(_) => {
+                let _ = fs::remove_file(&probe);
+                true
+            }
+            Err(_) => false,
+        }
     }
-}
 
-impl PartialEq for JavaRuntime {
+    /// Returns `true` if the `Path` has a root.
+    ///
+    /// Refer to [`Path::has_root`]
+    ///
     /// # Examples
     ///
     /// ```rust
     /// use java_runtimes::JavaRuntime;
     ///
-    /// let r1 = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
-    /// let r2 = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
-    /// let r3 = JavaRuntime::new("windows", r"D:\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
+    /// assert!(runtime.has_root());
+    ///
+    /// let runtime = JavaRuntime::new("linux", "../jdk/bin/java".as_ref(), "21.0.3").unwrap();
+    /// assert!(!runtime.has_root());
+    ///
+    /// # #[cfg(windows)] {
+    /// let runtime = JavaRuntime::new("windows", r"D:\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
+    /// assert!(runtime.has_root());
+    ///
+    /// let runtime = JavaRuntime::new("windows", r"..\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
+    /// assert!(!runtime.has_root());
+    /// # }
+    /// ```
+    pub fn has_root(&self) -> bool {
+        self.path.has_root()
+    }
+
+    /// Get the version string
+    ///
+    /// It's empty for runtimes from [`detector::detect_java_bin_dir_fast`] until [`JavaRuntime::update`] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
+    /// assert_eq!(runtime.get_version_string(), "21.0.3");
+    /// ```
+    pub fn get_version_string(&self) -> &str {
+        &self.version_string
+    }
+
+    /// Get the vendor parsed from the output of `java -version`, like `"OpenJDK"` or `"Oracle"`
+    ///
+    /// Returns `None` if the output has no recognizable runtime line, see [`JavaRuntime::extract_vendor`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let oracle = r#"java version "17.0.4.1" 2022-08-18 LTS
+    /// Java(TM) SE Runtime Environment (build 17.0.4.1+1-LTS-2)
+    /// Java HotSpot(TM) 64-Bit Server VM (build 17.0.4.1+1-LTS-2, mixed mode, sharing)
+    /// "#;
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), oracle).unwrap();
+    /// assert_eq!(runtime.get_vendor(), Some("Oracle"));
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.get_vendor(), None);
+    /// ```
+    pub fn get_vendor(&self) -> Option<&str> {
+        self.vendor.as_deref()
+    }
+
+    /// Classify the vendor of this runtime from the runtime line of `java -version` output, see [`Vendor::classify`]
+    ///
+    /// Returns [`Vendor::Other`] with an empty name if the output has no runtime line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::vendor::Vendor;
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let temurin = r#"openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK Runtime Environment Temurin-17.0.4.1+1 (build 17.0.4.1+1)
+    /// OpenJDK 64-Bit Server VM Temurin-17.0.4.1+1 (build 17.0.4.1+1, mixed mode, sharing)
+    /// "#;
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), temurin).unwrap();
+    /// assert_eq!(runtime.get_vendor(), Some("OpenJDK"));
+    /// assert_eq!(runtime.classify_vendor(), Vendor::Temurin);
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.classify_vendor(), Vendor::Other(String::new()));
+    /// ```
+    pub fn classify_vendor(&self) -> Vendor {
+        let info = self.version_info.as_ref();
+        let runtime_line = [
+            info.and_then(VersionInfo::get_runtime_name),
+            info.and_then(VersionInfo::get_build),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<&str>>()
+        .join(" ");
+        Vendor::classify(&runtime_line)
+    }
+
+    /// Get the structured `java -version` output, with runtime name, VM name and VM mode, see [`VersionInfo::parse`]
+    ///
+    /// Returns `None` if the output has neither the runtime line nor the VM line, e.g. this object was created
+    /// from a bare version string like `"17.0.4.1"`, or read from the `release` file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let output = r#"openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK Runtime Environment (build 17.0.4.1+1)
+    /// OpenJDK 64-Bit Server VM (build 17.0.4.1+1, mixed mode, sharing)
+    /// "#;
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), output).unwrap();
+    /// let info = runtime.get_version_info().unwrap();
+    /// assert_eq!(info.get_vm_name(), Some("OpenJDK 64-Bit Server VM"));
+    /// assert_eq!(info.get_vm_mode(), Some("mixed mode"));
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.get_version_info(), None);
+    /// ```
+    pub fn get_version_info(&self) -> Option<&VersionInfo> {
+        self.version_info.as_ref()
+    }
+
+    /// Get the major version number
+    ///
+    /// For the legacy scheme `1.x`, it's `x`. Otherwise it's the leading number,
+    /// ignoring suffixes like `-ea` of early-access builds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = |version: &str| JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), version).unwrap();
+    /// assert_eq!(runtime("1.8.0_333").get_major_version(), 8);
+    /// assert_eq!(runtime("17.0.4.1").get_major_version(), 17);
+    /// assert_eq!(runtime("21").get_major_version(), 21);
+    /// assert_eq!(runtime("17-ea").get_major_version(), 17);
+    /// ```
+    pub fn get_major_version(&self) -> u32 {
+        self.sort_key().0
+    }
+
+    /// Get `(major, minor, patch, update)` numbers of the version, for comparing
+    ///
+    /// The legacy scheme `1.8.0_333` is normalized to `(8, 0, 0, 333)`, so that runtimes of
+    /// mixed version schemes can be sorted by this key directly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = |version: &str| JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), version).unwrap();
+    ///
+    /// assert_eq!(runtime("1.8.0_292").sort_key(), (8, 0, 0, 292));
+    /// assert_eq!(runtime("17.0.4.1").sort_key(), (17, 0, 4, 1));
+    ///
+    /// let mut runtimes = vec![runtime("11.0.2"), runtime("21"), runtime("1.8.0_292"), runtime("17.0.4.1"), runtime("1.7.0_80")];
+    /// runtimes.sort_by_key(JavaRuntime::sort_key);
+    /// let versions = runtimes
+    ///     .iter()
+    ///     .map(|runtime| runtime.get_version_string())
+    ///     .collect::<Vec<&str>>();
+    /// assert_eq!(versions, ["1.7.0_80", "1.8.0_292", "11.0.2", "17.0.4.1", "21"]);
+    /// ```
+    pub fn sort_key(&self) -> (u32, u32, u32, u32) {
+        version_key(&self.version_string)
+    }
+
+    /// Get the update number of the version
+    ///
+    /// * For the legacy scheme, it's the number after `_`, like `333` in `1.8.0_333`
+    /// * Otherwise it's the third number, like `4` in `17.0.4.1`
+    ///
+    /// Returns `None` if the version has no such number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = |version: &str| JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), version).unwrap();
+    /// assert_eq!(runtime("1.8.0_333").get_update_number(), Some(333));
+    /// assert_eq!(runtime("1.8.0_333-b02").get_update_number(), Some(333));
+    /// assert_eq!(runtime("17.0.4.1+1-LTS-2").get_update_number(), Some(4));
+    /// assert_eq!(runtime("1.8.0").get_update_number(), None);
+    /// assert_eq!(runtime("21").get_update_number(), None);
+    /// ```
+    pub fn get_update_number(&self) -> Option<u32> {
+        let core = self.version_string.split(['+', '-']).next()?;
+        if core.starts_with("1.") {
+            core.split_once('_')?.1.parse().ok()
+        } else {
+            core.split('.').nth(2)?.parse().ok()
+        }
+    }
+
+    /// Get the build of this runtime, like `1-LTS-2` of `17.0.4.1+1-LTS-2`, or `b02` of `1.8.0_333-b02`
+    ///
+    /// It's parsed from the build string in the version output if present, see [`VersionInfo::get_build`],
+    /// otherwise from the version string.
+    ///
+    /// Returns `None` if there is no build.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = |version: &str| JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), version).unwrap();
+    /// assert_eq!(runtime("1.8.0_333-b02").get_build().unwrap(), "b02");
+    /// assert_eq!(runtime("17.0.4.1+1-LTS-2").get_build().unwrap(), "1-LTS-2");
+    /// assert_eq!(runtime("17.0.4.1").get_build(), None);
+    /// assert_eq!(runtime("17-ea").get_build(), None);
+    ///
+    /// let oracle = r#"java version "17.0.4.1" 2022-08-18 LTS
+    /// Java(TM) SE Runtime Environment (build 17.0.4.1+1-LTS-2)
+    /// Java HotSpot(TM) 64-Bit Server VM (build 17.0.4.1+1-LTS-2, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(runtime(oracle).get_version_string(), "17.0.4.1");
+    /// assert_eq!(runtime(oracle).get_build().unwrap(), "1-LTS-2");
+    ///
+    /// let oracle = r#"java version "1.8.0_333"
+    /// Java(TM) SE Runtime Environment (build 1.8.0_333-b02)
+    /// "#;
+    /// assert_eq!(runtime(oracle).get_build().unwrap(), "b02");
+    /// ```
+    pub fn get_build(&self) -> Option<String> {
+        let full = self
+            .version_info
+            .as_ref()
+            .and_then(VersionInfo::get_build)
+            .unwrap_or(&self.version_string);
+        let separator = if full.starts_with("1.") { '-' } else { '+' };
+        full.split_once(separator)
+            .map(|(_, build)| build.to_string())
+            .filter(|build| !build.is_empty())
+    }
+
+    /// Convert the version to a [`semver::Version`]
+    ///
+    /// The version is normalized as follows:
+    ///
+    /// * The legacy scheme `1.x.y_z` becomes `x.y.z`, like `1.8.0_333` to `8.0.333`
+    /// * Otherwise the first three numbers are `major.minor.patch`, missing ones are `0`, like `21` to `21.0.0`
+    /// * Further numbers become the build metadata, like `17.0.4.1` to `17.0.4+1`
+    /// * The pre-release suffix after `-` becomes the pre-release, like `17-ea` to `17.0.0-ea`
+    /// * The java build after `+`, and `-bNN` of the legacy scheme are dropped
+    ///
+    /// Returns `None` if the version can not be represented, e.g. the pre-release is not a valid identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use semver::Version;
+    ///
+    /// let runtime = |version: &str| JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), version).unwrap();
+    /// assert_eq!(runtime("1.8.0_333").to_semver(), Some(Version::parse("8.0.333").unwrap()));
+    /// assert_eq!(runtime("1.8.0_333-b02").to_semver(), Some(Version::parse("8.0.333").unwrap()));
+    /// assert_eq!(runtime("17.0.4.1").to_semver(), Some(Version::parse("17.0.4+1").unwrap()));
+    /// assert_eq!(runtime("17.0.4.1+1-LTS-2").to_semver(), Some(Version::parse("17.0.4+1").unwrap()));
+    /// assert_eq!(runtime("21").to_semver(), Some(Version::parse("21.0.0").unwrap()));
+    /// assert_eq!(runtime("17-ea").to_semver(), Some(Version::parse("17.0.0-ea").unwrap()));
+    ///
+    /// // Compare runtimes
+    /// let java8 = runtime("1.8.0_333").to_semver().unwrap();
+    /// let java17 = runtime("17.0.4").to_semver().unwrap();
+    /// let java17_1 = runtime("17.0.4.1").to_semver().unwrap();
+    /// let java17_ea = runtime("17-ea").to_semver().unwrap();
+    /// assert!(java8 < java17);
+    /// assert!(java17 < java17_1);
+    /// assert!(java17_ea < java17);
+    /// assert!(semver::VersionReq::parse(">=17").unwrap().matches(&java17));
+    /// assert!(!semver::VersionReq::parse(">=17").unwrap().matches(&java8));
+    /// ```
+    #[cfg(feature = "semver")]
+    pub fn to_semver(&self) -> Option<semver::Version> {
+        let version = self
+            .version_string
+            .split_once('+')
+            .map_or(self.version_string.as_str(), |(version, _)| version);
+        let (core, suffix) = version
+            .split_once('-')
+            .map_or((version, None), |(core, suffix)| (core, Some(suffix)));
+        let numbers = core
+            .split(['.', '_'])
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<u64>, _>>()
+            .ok()?;
+        let number = |i: usize| numbers.get(i).copied().unwrap_or(0);
+        if number(0) == 1 && numbers.len() > 1 {
+            return Some(semver::Version::new(number(1), number(2), number(3)));
+        }
+        let mut semver = semver::Version::new(number(0), number(1), number(2));
+        if numbers.len() > 3 {
+            let build = numbers[3..]
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<String>>()
+                .join(".");
+            semver.build = semver::BuildMetadata::new(&build).ok()?;
+        }
+        if let Some(suffix) = suffix {
+            semver.pre = semver::Prerelease::new(suffix).ok()?;
+        }
+        Some(semver)
+    }
+
+    /// Check if the major version of this runtime is at least the given one
+    ///
+    /// The legacy scheme is handled, so `1.8.0_333` is of major version 8.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "1.8.0_333").unwrap();
+    /// assert!(runtime.is_at_least(8));
+    /// assert!(!runtime.is_at_least(9));
+    /// assert!(runtime.is_at_most(8));
+    /// assert!(runtime.is_exactly(8));
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert!(runtime.is_at_least(11));
+    /// assert!(!runtime.is_at_most(11));
+    /// assert!(!runtime.is_exactly(1));
+    /// ```
+    pub fn is_at_least(&self, major: u32) -> bool {
+        self.get_major_version() >= major
+    }
+
+    /// Check if the major version of this runtime is at most the given one, see [`JavaRuntime::is_at_least`]
+    pub fn is_at_most(&self, major: u32) -> bool {
+        self.get_major_version() <= major
+    }
+
+    /// Check if the major version of this runtime is the given one, see [`JavaRuntime::is_at_least`]
+    pub fn is_exactly(&self, major: u32) -> bool {
+        self.get_major_version() == major
+    }
+
+    /// Check if this runtime is of the given major version, and at least the given version
+    ///
+    /// It's useful for requiring security updates, like "Java 17 at least 17.0.8".
+    /// Runtimes of other major versions never meet the minimum.
+    ///
+    /// # Parameters
+    ///
+    /// * `major` The major version, like `17` or `8`
+    /// * `minimum` The minimum version, like `"17.0.8"` or `"1.8.0_321"`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert!(!runtime.meets_minimum(17, "17.0.8"));
+    /// assert!(runtime.meets_minimum(17, "17.0.4"));
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.9").unwrap();
+    /// assert!(runtime.meets_minimum(17, "17.0.8"));
+    /// assert!(!runtime.meets_minimum(21, "21.0.1"));
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "1.8.0_333").unwrap();
+    /// assert!(runtime.meets_minimum(8, "1.8.0_321"));
+    /// ```
+    pub fn meets_minimum(&self, major: u32, minimum: &str) -> bool {
+        self.get_major_version() == major && self.sort_key() >= version_key(minimum)
+    }
+
+    /// Check if the version in the name of java home directory matches the reported version
+    ///
+    /// It's useful for finding installations upgraded in place, whose directory claims an outdated version.
+    /// The directory version matches if the reported version starts with it, like `jdk-17.0.4` and `17.0.4.1`.
+    /// A single number in the directory name is compared with the major version, like `jdk8u333` and `1.8.0_333`.
+    ///
+    /// Returns `None` if the java home is unknown or its name contains no version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = |path: &str, version: &str| JavaRuntime::new("linux", path.as_ref(), version).unwrap();
+    ///
+    /// assert_eq!(runtime("/opt/jdk-17.0.4/bin/java", "17.0.4.1").dir_version_matches_reported(), Some(true));
+    /// assert_eq!(runtime("/opt/jdk1.8.0_333/bin/java", "1.8.0_333").dir_version_matches_reported(), Some(true));
+    /// assert_eq!(runtime("/opt/jdk8u333/bin/java", "1.8.0_333").dir_version_matches_reported(), Some(true));
+    /// assert_eq!(runtime("/usr/lib/jvm/java-17-openjdk/bin/java", "17.0.8").dir_version_matches_reported(), Some(true));
+    ///
+    /// assert_eq!(runtime("/opt/jdk-17.0.4/bin/java", "17.0.8").dir_version_matches_reported(), Some(false));
+    /// assert_eq!(runtime("/opt/jdk-17.0.4/bin/java", "17.0.41").dir_version_matches_reported(), Some(false));
+    /// assert_eq!(runtime("/opt/jdk-11/bin/java", "17.0.8").dir_version_matches_reported(), Some(false));
+    ///
+    /// assert_eq!(runtime("/opt/jdk/bin/java", "17.0.8").dir_version_matches_reported(), None);
+    /// ```
+    pub fn dir_version_matches_reported(&self) -> Option<bool> {
+        let home = self.get_home()?;
+        let dir_name = home.file_name()?.to_string_lossy().to_string();
+        let dir_version = Regex::new(Self::DIR_VERSION_PATTERN)
+            .unwrap()
+            .find(&dir_name)?
+            .as_str()
+            .to_string();
+        if !dir_version.contains(['.', '_']) {
+            return Some(dir_version.parse::<u32>().ok()? == self.get_major_version());
+        }
+        let rest = self.version_string.strip_prefix(&dir_version);
+        Some(rest.is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit())))
+    }
+
+    /// Check if this runtime contains the given module, like `java.sql` or `java.desktop`
+    ///
+    /// Images created by `jlink` may leave out modules. The modules are read from the `MODULES` entry
+    /// of the `release` file in java home. If it's absent, command `java --list-modules` is executed instead.
+    ///
+    /// Returns `false` for runtimes without modules, i.e. Java 8 and earlier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// let home = env::temp_dir().join("java-runtimes-doctest-has-module/jlink-image");
+    /// fs::create_dir_all(&home).unwrap();
+    /// let release = "JAVA_VERSION=\"17.0.4.1\"\nMODULES=\"java.base java.logging java.sql\"\n";
+    /// fs::write(home.join("release"), release).unwrap();
+    ///
+    /// let runtime = JavaRuntime::new("linux", &home.join("bin/java"), "17.0.4.1").unwrap();
+    /// assert!(runtime.has_module("java.base"));
+    /// assert!(runtime.has_module("java.sql"));
+    /// assert!(!runtime.has_module("java.desktop"));
+    /// assert!(!runtime.has_module("java"));
+    /// ```
+    pub fn has_module(&self, module: &str) -> bool {
+        if let Some(modules) = self.release_entry("MODULES") {
+            return modules.split_whitespace().any(|m| m == module);
+        }
+        let Ok(output) = Command::new(&self.path).arg("--list-modules").output() else {
+            return false;
+        };
+        output.status.success()
+            && String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|line| line.trim().split('@').next() == Some(module))
+    }
+
+    /// Check if this runtime supports virtual threads, which are available since Java 21
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert!(!runtime.supports_virtual_threads());
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.1").unwrap();
+    /// assert!(runtime.supports_virtual_threads());
+    /// ```
+    pub fn supports_virtual_threads(&self) -> bool {
+        self.get_major_version() >= 21
+    }
+
+    /// Check if this runtime is a long-term support release
+    ///
+    /// LTS releases are 8, 11, and every 4th release since 17 (17, 21, 25, 29 ...).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// for version in ["1.8.0_333", "11.0.2", "17.0.4.1", "21.0.1", "25.0.1"] {
+    ///     let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), version).unwrap();
+    ///     assert!(runtime.is_lts(), "{}", version);
+    /// }
+    /// for version in ["9.0.4", "16.0.2", "18.0.2", "22.0.1"] {
+    ///     let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), version).unwrap();
+    ///     assert!(!runtime.is_lts(), "{}", version);
+    /// }
+    /// ```
+    pub fn is_lts(&self) -> bool {
+        match self.get_major_version() {
+            8 | 11 => true,
+            major => major >= 17 && (major - 17) % 4 == 0,
+        }
+    }
+
+    /// Check if this runtime honors the CPU and memory limits of the container
+    ///
+    /// Container support (`-XX:+UseContainerSupport`) is available since Java 10, and was backported to 8u191.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "1.8.0_131").unwrap();
+    /// assert!(!runtime.is_container_aware());
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "1.8.0_191").unwrap();
+    /// assert!(runtime.is_container_aware());
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "9.0.4").unwrap();
+    /// assert!(!runtime.is_container_aware());
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "11.0.2").unwrap();
+    /// assert!(runtime.is_container_aware());
+    /// ```
+    pub fn is_container_aware(&self) -> bool {
+        match self.sort_key() {
+            (8, _, _, update) => update >= 191,
+            (major, ..) => major >= 10,
+        }
+    }
+
+    /// Check if this runtime is a JDK, which has a `javac` next to the java executable file
+    ///
+    /// It only checks the filesystem, without executing java.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// let root = env::temp_dir().join("java-runtimes-doctest-is-jdk");
+    ///
+    /// let jdk = root.join("jdk-17/bin");
+    /// fs::create_dir_all(&jdk).unwrap();
+    /// fs::write(jdk.join("java"), "").unwrap();
+    /// fs::write(jdk.join("javac"), "").unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk.join("java"), "17.0.4.1").unwrap();
+    /// assert!(runtime.is_jdk());
+    /// assert!(!runtime.is_jre());
+    ///
+    /// let jre = root.join("jre-17/bin");
+    /// fs::create_dir_all(&jre).unwrap();
+    /// fs::write(jre.join("java"), "").unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jre.join("java"), "17.0.4.1").unwrap();
+    /// assert!(!runtime.is_jdk());
+    /// assert!(runtime.is_jre());
+    ///
+    /// let runtime = JavaRuntime::new("windows", &jdk.join("java.exe"), "17.0.4.1").unwrap();
+    /// assert!(!runtime.is_jdk());
+    /// ```
+    pub fn is_jdk(&self) -> bool {
+        self.has_tool("javac")
+    }
+
+    /// Check if this runtime is a JRE, whose java executable file exists but has no `javac` next to it
+    ///
+    /// See [`JavaRuntime::is_jdk`]
+    pub fn is_jre(&self) -> bool {
+        self.path.is_file() && !self.is_jdk()
+    }
+
+    /// Check if this runtime ships the Flight Recorder tooling
+    ///
+    /// It requires Java 11+ with the `jfr` tool next to the java executable file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// let root = env::temp_dir().join("java-runtimes-doctest-jfr");
+    ///
+    /// let jdk8 = root.join("jdk-1.8/bin");
+    /// fs::create_dir_all(&jdk8).unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk8.join("java"), "1.8.0_333").unwrap();
+    /// assert!(!runtime.supports_jfr());
+    ///
+    /// let jdk17 = root.join("jdk-17/bin");
+    /// fs::create_dir_all(&jdk17).unwrap();
+    /// fs::write(jdk17.join("jfr"), "").unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk17.join("java"), "17.0.4.1").unwrap();
+    /// assert!(runtime.supports_jfr());
+    /// ```
+    pub fn supports_jfr(&self) -> bool {
+        self.get_major_version() >= 11 && self.has_tool("jfr")
+    }
+
+    /// Check if there is an executable file of the given name next to the java executable file
+    ///
+    /// * `name` Name without suffix, like `javac`
+    fn has_tool(&self, name: &str) -> bool {
+        let file_name = if self.is_windows() {
+            format!("{}.exe", name)
+        } else {
+            name.to_string()
+        };
+        self.path.with_file_name(file_name).is_file()
+    }
+
+    /// Check if this is the same os as current
+    pub fn is_same_os(&self) -> bool {
+        self.os == env::consts::OS
+    }
+
+    /// Create a new [`JavaRuntime`] with absolute path.
+    ///
+    /// A relative path is resolved against the current working directory,
+    /// while an absolute path is returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Err`] if the current working directory value is invalid. Refer to [`env::current_dir`]
+    ///
+    /// Possible cases:
+    ///
+    /// * Current directory does not exist.
+    /// * There are insufficient permissions to access the current directory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::env;
+    /// use std::path::Path;
+    ///
+    /// let relative = Path::new("runtimes/jdk-17/bin/java");
+    /// let runtime = JavaRuntime::new("linux", relative, "17.0.4.1").unwrap();
+    /// let absolute = runtime.to_absolute().unwrap();
+    /// assert_eq!(absolute.get_executable(), env::current_dir().unwrap().join(relative));
+    /// assert_eq!(absolute.get_version_string(), "17.0.4.1");
+    ///
+    /// let absolute_path = env::temp_dir().join("jdk-17/bin/java");
+    /// let runtime = JavaRuntime::new("linux", &absolute_path, "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.to_absolute().unwrap().get_executable(), absolute_path);
+    /// ```
+    pub fn to_absolute(&self) -> Result<Self, Error> {
+        let cwd = env::current_dir().or(Err(Error::new(ErrorKind::InvalidWorkDir)))?;
+        let path_absolute = cwd.join(&self.path);
+        let new_runtime = Self::new(&self.os, &path_absolute, &self.version_string)?;
+        Ok(new_runtime)
+    }
+
+    /// Create a copy of this [`JavaRuntime`] with the canonical path of java executable file
+    ///
+    /// Symbolic links are resolved, so that a runtime found via a link like `/usr/bin/java`
+    /// equals the one found in java home.
+    ///
+    /// # Errors
+    ///
+    /// * [`ErrorKind::CanonicalizingFailed`] if the path does not exist or can not be resolved, see [`fs::canonicalize`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::{env, fs};
+    /// use java_runtimes::error::ErrorKind;
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-canonicalized");
+    /// # fs::create_dir_all(root.join("jdk-17/bin")).unwrap();
+    /// # fs::create_dir_all(root.join("usr/bin")).unwrap();
+    /// # fs::write(root.join("jdk-17/bin/java"), "").unwrap();
+    /// # let _ = fs::remove_file(root.join("usr/bin/java"));
+    /// // root/usr/bin/java -> root/jdk-17/bin/java
+    /// std::os::unix::fs::symlink(root.join("jdk-17/bin/java"), root.join("usr/bin/java")).unwrap();
+    ///
+    /// let linked = JavaRuntime::new("linux", &root.join("usr/bin/java"), "17.0.4.1").unwrap();
+    /// let real = JavaRuntime::new("linux", &root.join("jdk-17/bin/java"), "17.0.4.1").unwrap();
+    /// assert_ne!(linked, real);
+    ///
+    /// let canonicalized = linked.canonicalized().unwrap();
+    /// assert_eq!(canonicalized.get_executable(), root.join("jdk-17/bin/java").canonicalize().unwrap());
+    /// assert_eq!(canonicalized, real.canonicalized().unwrap());
+    /// assert_eq!(canonicalized.get_version_string(), "17.0.4.1");
+    ///
+    /// let missing = JavaRuntime::new("linux", &root.join("missing/bin/java"), "17.0.4.1").unwrap();
+    /// let err = missing.canonicalized().unwrap_err();
+    /// assert!(matches!(err.kind(), ErrorKind::CanonicalizingFailed(_)));
+    /// # }
+    /// ```
+    pub fn canonicalized(&self) -> Result<Self, Error> {
+        let path = self.canonical_path()?;
+        let mut canonicalized = self.clone();
+        canonicalized.path = path;
+        Ok(canonicalized)
+    }
+
+    /// Get the canonical path of the java executable file, see [`JavaRuntime::canonicalized`]
+    pub(crate) fn canonical_path(&self) -> Result<PathBuf, Error> {
+        self.path
+            .canonicalize()
+            .map_err(|err| Error::new(ErrorKind::CanonicalizingFailed(err)))
+    }
+
+    /// Get the cryptographic policy of this runtime
+    ///
+    /// The policy is read from the `crypto.policy` property in `java.security` if it's set.
+    /// Otherwise it's guessed from the layout of the security directory in java home:
+    ///
+    /// * `conf/security/policy` (Java 9+): `"unlimited"`, the default since Java 9
+    /// * `lib/security/local_policy.jar` (Java 8 and earlier): `"limited"`, the default of the shipped policy jars
+    ///
+    /// # Returns
+    ///
+    /// * `Some(String)` like `"limited"` or `"unlimited"`
+    /// * `None` if no policy files found
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// let root = env::temp_dir().join("java-runtimes-doctest-crypto-policy");
+    ///
+    /// let jdk8 = root.join("jdk-1.8.0_131");
+    /// fs::create_dir_all(jdk8.join("jre/lib/security")).unwrap();
+    /// fs::write(jdk8.join("jre/lib/security/local_policy.jar"), "").unwrap();
+    /// fs::write(jdk8.join("jre/lib/security/US_export_policy.jar"), "").unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk8.join("bin/java"), "1.8.0_131").unwrap();
+    /// assert_eq!(runtime.crypto_policy().unwrap(), "limited");
+    ///
+    /// let jdk11 = root.join("jdk-11.0.2");
+    /// fs::create_dir_all(jdk11.join("conf/security/policy/unlimited")).unwrap();
+    /// fs::write(jdk11.join("conf/security/java.security"), "#crypto.policy=limited\n").unwrap();
+    /// let runtime = JavaRuntime::new("linux", &jdk11.join("bin/java"), "11.0.2").unwrap();
+    /// assert_eq!(runtime.crypto_policy().unwrap(), "unlimited");
+    ///
+    /// fs::write(jdk11.join("conf/security/java.security"), "crypto.policy=limited\n").unwrap();
+    /// assert_eq!(runtime.crypto_policy().unwrap(), "limited");
+    ///
+    /// let runtime = JavaRuntime::new("linux", &root.join("empty/bin/java"), "11.0.2").unwrap();
+    /// assert_eq!(runtime.crypto_policy(), None);
+    /// ```
+    pub fn crypto_policy(&self) -> Option<String> {
+        let home = self.get_home()?;
+        // Java 9+ keeps them in `conf/security`, Java 8 JDK in `jre/lib/security`
+        let security_dirs = [
+            home.join("conf").join("security"),
+            home.join("lib").join("security"),
+            home.join("jre").join("lib").join("security"),
+        ];
+
+        for security_dir in &security_dirs {
+            let properties =
+                fs::read_to_string(security_dir.join("java.security")).unwrap_or_default();
+            let policy = properties
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("crypto.policy"))
+                .filter_map(|rest| rest.trim_start().strip_prefix('='))
+                .map(|value| value.trim())
+                .next_back();
+            if let Some(policy) = policy {
+                return Some(policy.to_string());
+            }
+        }
+
+        if security_dirs[0].join("policy").is_dir() {
+            Some("unlimited".to_string())
+        } else if security_dirs[1..]
+            .iter()
+            .any(|dir| dir.join("local_policy.jar").is_file())
+        {
+            Some("limited".to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Generate a script that launches this runtime with the given arguments
+    ///
+    /// The kind of script depends on the os of this runtime:
+    ///
+    /// * `windows`: a batch file starting with `@echo off`
+    /// * others: a `#!/bin/sh` shell script
+    ///
+    /// Paths and arguments are quoted, and arguments passed to the script are appended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/opt/my jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert_eq!(
+    ///     runtime.generate_launcher_script(&["-jar", "it's.jar"]),
+    ///     "#!/bin/sh\nexec '/opt/my jdk/bin/java' '-jar' 'it'\\''s.jar' \"$@\"\n"
+    /// );
+    ///
+    /// let runtime = JavaRuntime::new("windows", r"C:\Program Files\jdk\bin\java.exe".as_ref(), "17.0.4.1").unwrap();
+    /// assert_eq!(
+    ///     runtime.generate_launcher_script(&["-Dname=\"100%\"", "-jar", "app.jar"]),
+    ///     "@echo off\r\n\"C:\\Program Files\\jdk\\bin\\java.exe\" \"-Dname=\"\"100%%\"\"\" \"-jar\" \"app.jar\" %*\r\n"
+    /// );
+    /// ```
+    pub fn generate_launcher_script(&self, args: &[&str]) -> String {
+        let path = self.path.to_string_lossy();
+        if self.is_windows() {
+            let quote = |s: &str| format!("\"{}\"", s.replace('"', "\"\"").replace('%', "%%"));
+            let mut command = vec![quote(&path)];
+            command.extend(args.iter().map(|arg| quote(arg)));
+            command.push("%*".to_string());
+            format!("@echo off\r\n{}\r\n", command.join(" "))
+        } else {
+            let quote = |s: &str| format!("'{}'", s.replace('\'', r"'\''"));
+            let mut command = vec![quote(&path)];
+            command.extend(args.iter().map(|arg| quote(arg)));
+            command.push("\"$@\"".to_string());
+            format!("#!/bin/sh\nexec {}\n", command.join(" "))
+        }
+    }
+
+    /// Try executing `java -version` and parse the output to get the version.
+    ///
+    /// Java is executed with `-Djava.awt.headless=true`, so that JVMs trying to initialize AWT
+    /// don't fail on machines without a display. See [`CommandProber`].
+    ///
+    /// If success, it will update the version value in this [`JavaRuntime`] instance.
+    ///
+    /// # Errors
+    ///
+    /// * [`ErrorKind::CorruptExecutable`] if the java executable file is empty or not executable, e.g. truncated.
+    /// * [`ErrorKind::EmptyVersionOutput`] if java succeeded but printed nothing.
+    /// * [`ErrorKind::NoJavaVersionStringFound`] if java printed something that contains no version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let mut runtime = JavaRuntime::new("linux", "/usr/lib/jvm/jdk-17/bin/java".as_ref(), "17").unwrap();
+    /// match runtime.update() {
+    ///     Ok(()) => println!("Java version: {}", runtime.get_version_string()),
+    ///     Err(err) => println!("Failed to get the version: {}", err),
+    /// }
+    /// ```
+    pub fn update(&mut self) -> Result<(), Error> {
+        self.update_with_prober(&CommandProber::new())
+    }
+
+    /// Same as [`JavaRuntime::update`], but the version output is got by the given prober
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::error::{Error, ErrorKind};
+    /// use java_runtimes::prober::VersionProber;
+    /// use java_runtimes::JavaRuntime;
+    /// use std::path::Path;
+    ///
+    /// #[derive(Debug)]
+    /// struct MockProber(&'static str);
+    ///
+    /// impl VersionProber for MockProber {
+    ///     fn probe(&self, _executable: &Path) -> Result<String, Error> {
+    ///         Ok(self.0.to_string())
+    ///     }
+    /// }
+    ///
+    /// let mut runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    ///
+    /// runtime.update_with_prober(&MockProber("java version \"1.8.0_333\"")).unwrap();
+    /// assert_eq!(runtime.get_version_string(), "1.8.0_333");
+    ///
+    /// runtime.update_with_prober(&MockProber("openjdk version \"21.0.1\" 2023-10-17")).unwrap();
+    /// assert_eq!(runtime.get_version_string(), "21.0.1");
+    ///
+    /// let err = runtime.update_with_prober(&MockProber("")).unwrap_err();
+    /// assert!(matches!(err.kind(), ErrorKind::EmptyVersionOutput(_)));
+    /// ```
+    pub fn update_with_prober(&mut self, prober: &dyn VersionProber) -> Result<(), Error> {
+        let output = prober.probe(&self.path)?;
+        if output.trim().is_empty() {
+            return Err(Error::new(ErrorKind::EmptyVersionOutput(self.path.clone())));
+        }
+        let version_output = properties::strip_properties(&output);
+        self.version_string = Self::extract_version(&version_output)?;
+        self.vendor = Self::extract_vendor(&version_output);
+        self.arch = properties::parse_properties(&output, ':').remove("os.arch");
+        self.version_info = Self::parse_version_info(&version_output);
+        Ok(())
+    }
+
+    /// Test if this runtime is available currently
+    ///
+    /// It executes command `java -version` to see if it works
+    pub fn is_available(&self) -> bool {
+        self.is_same_os() && Self::from_executable(&self.path).is_ok()
+    }
+
+    /// Test if this runtime can be used on this machine right now
+    ///
+    /// This is stricter than [`JavaRuntime::is_available`], it requires all of these:
+    ///
+    /// * It's the same os as current.
+    /// * Its architecture matches the current one, if known.
+    /// * The java executable file exists.
+    /// * Command `java -version` works.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/usr/lib/jvm/jdk-17/bin/java".as_ref(), "17").unwrap();
+    /// if !runtime.is_usable_here() {
+    ///     println!("{:?} can't be used on this machine", runtime.get_executable());
+    /// }
+    /// ```
+    pub fn is_usable_here(&self) -> bool {
+        self.is_same_os()
+            && self.is_same_arch() != Some(false)
+            && self.path.is_file()
+            && Self::from_executable(&self.path).is_ok()
+    }
+
+    /// Check if the architecture of this runtime is the same as current
+    ///
+    /// Returns `None` if the architecture is unknown.
+    pub(crate) fn is_same_arch(&self) -> Option<bool> {
+        self.is_arch(env::consts::ARCH)
+    }
+
+    /// Check if the architecture of this runtime is the given one, like `aarch64` or `x86_64`
+    ///
+    /// Returns `None` if the architecture is unknown.
+    pub(crate) fn is_arch(&self, arch: &str) -> Option<bool> {
+        let known_arch = self.known_arch()?;
+        Some(normalize_arch(&known_arch) == normalize_arch(arch))
+    }
+
+    /// Get the architecture reported by java, or the `OS_ARCH` entry of the `release` file in java home
+    pub(crate) fn known_arch(&self) -> Option<String> {
+        self.arch.clone().or_else(|| self.release_entry("OS_ARCH"))
+    }
+
+    /// Get the architecture of this runtime, like `amd64` or `aarch64`
+    ///
+    /// It's the `os.arch` system property printed by `java -XshowSettings:properties -version` when the runtime was updated.
+    /// Returns `None` if the runtime was created manually, or java does not support the option.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::error::Error;
+    /// use java_runtimes::prober::VersionProber;
+    /// use java_runtimes::JavaRuntime;
+    /// use std::path::Path;
+    ///
+    /// #[derive(Debug)]
+    /// struct MockProber(&'static str);
+    ///
+    /// impl VersionProber for MockProber {
+    ///     fn probe(&self, _executable: &Path) -> Result<String, Error> {
+    ///         Ok(self.0.to_string())
+    ///     }
+    /// }
+    ///
+    /// let output = r#"Property settings:
+    ///     java.home = /usr/lib/jvm/temurin-17-jdk-arm64
+    ///     java.runtime.name = OpenJDK Runtime Environment
+    ///     os.arch = aarch64
+    ///     os.name = Linux
+    ///
+    /// openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK Runtime Environment Temurin-17.0.4.1+1 (build 17.0.4.1+1)
+    /// OpenJDK 64-Bit Server VM Temurin-17.0.4.1+1 (build 17.0.4.1+1, mixed mode, sharing)
+    /// "#;
+    /// let mut runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.get_arch(), None);
+    ///
+    /// runtime.update_with_prober(&MockProber(output)).unwrap();
+    /// assert_eq!(runtime.get_arch(), Some("aarch64"));
+    /// assert_eq!(runtime.get_version_string(), "17.0.4.1");
+    /// assert_eq!(runtime.get_vendor(), Some("OpenJDK"));
+    ///
+    /// // Very old JVMs print only the version
+    /// let output = "java version \"1.6.0_45\"\nJava(TM) SE Runtime Environment (build 1.6.0_45-b06)\n";
+    /// runtime.update_with_prober(&MockProber(output)).unwrap();
+    /// assert_eq!(runtime.get_arch(), None);
+    /// ```
+    pub fn get_arch(&self) -> Option<&str> {
+        self.arch.as_deref()
+    }
+
+    /// Get the vendor from the `IMPLEMENTOR` entry of the `release` file in java home
+    pub(crate) fn known_vendor(&self) -> Option<String> {
+        self.release_entry("IMPLEMENTOR")
+    }
+
+    /// Get the precise distribution build of this runtime, like `Temurin-17.0.4.1+1`
+    ///
+    /// It's read from the `IMPLEMENTOR_VERSION` entry of the `release` file in java home,
+    /// which differs from the java version and the vendor name.
+    ///
+    /// Returns `None` if the release file or the entry does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// let home = env::temp_dir().join("java-runtimes-doctest-implementor-version/jdk-17");
+    /// fs::create_dir_all(&home).unwrap();
+    /// let release = r#"IMPLEMENTOR="Eclipse Adoptium"
+    /// IMPLEMENTOR_VERSION="Temurin-17.0.4.1+1"
+    /// JAVA_VERSION="17.0.4.1"
+    /// "#;
+    /// fs::write(home.join("release"), release).unwrap();
+    ///
+    /// let runtime = JavaRuntime::new("linux", &home.join("bin/java"), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.implementor_version().unwrap(), "Temurin-17.0.4.1+1");
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/missing/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.implementor_version(), None);
+    /// ```
+    pub fn implementor_version(&self) -> Option<String> {
+        self.release_entry("IMPLEMENTOR_VERSION")
+    }
+
+    /// Check if the other runtime would behave the same as this one when launching programs
+    ///
+    /// They must have the same major version, vendor and architecture, while patch level and path are ignored.
+    /// The vendor is read from the `release` file in java home, and so is the architecture unless java reported it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-compatible");
+    /// # let runtime = |home: &str, version: &str| {
+    /// #     fs::create_dir_all(root.join(home)).unwrap();
+    /// #     let release = "IMPLEMENTOR=\"Eclipse Adoptium\"\nOS_ARCH=\"x86_64\"\n";
+    /// #     fs::write(root.join(home).join("release"), release).unwrap();
+    /// #     JavaRuntime::new("linux", &root.join(home).join("bin/java"), version).unwrap()
+    /// # };
+    /// let current = runtime("temurin-17.0.4", "17.0.4.1");
+    /// let candidate = runtime("temurin-17.0.8", "17.0.8");
+    /// let newer = runtime("temurin-21.0.1", "21.0.1");
+    ///
+    /// assert!(current.is_compatible_with(&candidate));
+    /// assert!(!current.is_compatible_with(&newer));
+    /// ```
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        let arch = |runtime: &Self| {
+            runtime
+                .known_arch()
+                .map(|arch| normalize_arch(&arch).to_string())
+        };
+        self.get_major_version() == other.get_major_version()
+            && self.known_vendor() == other.known_vendor()
+            && arch(self) == arch(other)
+    }
+
+    /// Check if this runtime is an upgrade of the other one
+    ///
+    /// They must have the same major version and vendor, and this one must have a strictly higher version.
+    /// The vendor is read from the `release` file in java home.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::{env, fs};
+    ///
+    /// # let root = env::temp_dir().join("java-runtimes-doctest-upgrade");
+    /// # let runtime = |home: &str, version: &str, vendor: &str| {
+    /// #     fs::create_dir_all(root.join(home)).unwrap();
+    /// #     fs::write(root.join(home).join("release"), format!("IMPLEMENTOR=\"{}\"\n", vendor)).unwrap();
+    /// #     JavaRuntime::new("linux", &root.join(home).join("bin/java"), version).unwrap()
+    /// # };
+    /// let installed = runtime("temurin-17.0.4", "17.0.4.1", "Eclipse Adoptium");
+    /// let patched = runtime("temurin-17.0.8", "17.0.8", "Eclipse Adoptium");
+    /// let next_major = runtime("temurin-21.0.1", "21.0.1", "Eclipse Adoptium");
+    /// let other_vendor = runtime("corretto-17.0.8", "17.0.8", "Amazon.com Inc.");
+    ///
+    /// assert!(patched.is_upgrade_of(&installed));
+    /// assert!(!installed.is_upgrade_of(&patched));
+    /// assert!(!installed.is_upgrade_of(&installed));
+    /// assert!(!next_major.is_upgrade_of(&installed));
+    /// assert!(!other_vendor.is_upgrade_of(&installed));
+    /// ```
+    pub fn is_upgrade_of(&self, other: &Self) -> bool {
+        self.get_major_version() == other.get_major_version()
+            && self.known_vendor() == other.known_vendor()
+            && self.sort_key() > other.sort_key()
+    }
+
+    /// Parse version string
+    ///
+    /// # Return
+    ///
+    /// `(version_string, version_major)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// assert_eq!(JavaRuntime::extract_version("1.8.0_333").unwrap(), "1.8.0_333");
+    /// assert_eq!(JavaRuntime::extract_version("17.0.4.1").unwrap(), "17.0.4.1");
+    /// assert_eq!(JavaRuntime::extract_version("\"17.0.4.1").unwrap(), "17.0.4.1");
+    /// assert_eq!(JavaRuntime::extract_version("java version \"17.0.4.1\"").unwrap(), "17.0.4.1");
+    /// assert_eq!(JavaRuntime::extract_version("openjdk version \"17.0.4.1\"").unwrap(), "17.0.4.1");
+    /// assert_eq!(JavaRuntime::extract_version("x version \"11.0.2\" y version \"17.0.4.1\"").unwrap(), "11.0.2");
+    /// ```
+    ///
+    /// Bare major versions of GA releases, and suffixes of pre-releases and builds are kept
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// assert_eq!(JavaRuntime::extract_version("21").unwrap(), "21");
+    /// assert_eq!(JavaRuntime::extract_version("openjdk version \"21\" 2023-09-19").unwrap(), "21");
+    /// assert_eq!(JavaRuntime::extract_version("17-ea").unwrap(), "17-ea");
+    /// assert_eq!(JavaRuntime::extract_version("openjdk version \"17-ea\" 2021-09-14").unwrap(), "17-ea");
+    /// assert_eq!(JavaRuntime::extract_version("21.0.1-beta").unwrap(), "21.0.1-beta");
+    /// assert_eq!(JavaRuntime::extract_version("21.0.1+12").unwrap(), "21.0.1+12");
+    /// assert_eq!(JavaRuntime::extract_version("openjdk 22-ea 2024-03-19").unwrap(), "22-ea");
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17-ea").unwrap();
+    /// assert_eq!(runtime.get_major_version(), 17);
+    /// assert!(JavaRuntime::extract_version("-ea").is_err());
+    /// ```
+    ///
+    /// The unquoted version printed to stdout by `java --version` is also accepted
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let openjdk = r#"openjdk 17.0.4.1 2022-08-12
+    /// OpenJDK Runtime Environment (build 17.0.4.1+1)
+    /// OpenJDK 64-Bit Server VM (build 17.0.4.1+1, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_version(openjdk).unwrap(), "17.0.4.1");
+    ///
+    /// let oracle = r#"java 17.0.4.1 2022-08-18 LTS
+    /// Java(TM) SE Runtime Environment (build 17.0.4.1+1-LTS-2)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_version(oracle).unwrap(), "17.0.4.1");
+    ///
+    /// assert_eq!(JavaRuntime::extract_version("openjdk 21 2023-09-19").unwrap(), "21");
+    ///
+    /// // The quoted legacy form still wins
+    /// assert_eq!(JavaRuntime::extract_version("openjdk version \"11.0.2\" 2019-01-15").unwrap(), "11.0.2");
+    /// assert!(JavaRuntime::extract_version("openjdk version").is_err());
+    /// ```
+    ///
+    /// The first quoted version is chosen from multi-line outputs, whichever line it is on
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let oracle = r#"java version "1.8.0_333"
+    /// Java(TM) SE Runtime Environment (build 1.8.0_333-b02)
+    /// Java HotSpot(TM) 64-Bit Server VM (build 25.333-b02, mixed mode)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_version(oracle).unwrap(), "1.8.0_333");
+    ///
+    /// let openjdk = r#"openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK Runtime Environment (build 17.0.4.1+1)
+    /// OpenJDK 64-Bit Server VM (build 17.0.4.1+1, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_version(openjdk).unwrap(), "17.0.4.1");
+    ///
+    /// let runtime_line_first = r#"OpenJDK Runtime Environment (build 11.0.2+9, "vendor 11.0")
+    /// openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK 64-Bit Server VM (build 17.0.4.1+1, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_version(runtime_line_first).unwrap(), "17.0.4.1");
+    ///
+    /// // Quoted numbers in other lines are not versions
+    /// let picked_up = r#"Picked up JAVA_TOOL_OPTIONS: -Dfoo="5"
+    /// openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK Runtime Environment (build 17.0.4.1+1)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_version(picked_up).unwrap(), "17.0.4.1");
+    /// assert!(JavaRuntime::extract_version("Picked up JAVA_TOOL_OPTIONS: -Dfoo=\"5\"").is_err());
+    /// ```
+    pub fn extract_version(version_string: &str) -> Result<String, Error> {
+        let quoted = Regex::new(Self::VERSION_PATTERN)
+            .unwrap()
+            .captures(&format!("\"{}\"", &version_string))
+            .and_then(|captures| captures.get(1))
+            .map(|version| version.as_str().to_string());
+        quoted
+            .or_else(|| {
+                Regex::new(Self::MODERN_VERSION_PATTERN)
+                    .unwrap()
+                    .captures(version_string)?
+                    .get(1)
+                    .map(|version| version.as_str().to_string())
+            })
+            .ok_or(Error::new(ErrorKind::NoJavaVersionStringFound))
+    }
+
+    /// Get the full build string of this runtime, like `17.0.4.1+1-LTS-2`
+    ///
+    /// It's the build parsed from the version output, see [`VersionInfo::get_build`].
+    /// If there is none, e.g. this object was created from a bare version string,
+    /// it executes command `java -version` to get the version output, see [`JavaRuntime::extract_full_build_string`]
+    ///
+    /// Returns `None` if failed to execute java or the output contains no build string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// # let bin = env::temp_dir().join("java-runtimes-doctest-build-string/jdk-17/bin");
+    /// # fs::create_dir_all(&bin).unwrap();
+    /// # let script = r#"#!/bin/sh
+    /// # echo 'java version "17.0.4.1" 2022-08-18 LTS' >&2
+    /// # echo 'Java(TM) SE Runtime Environment (build 17.0.4.1+1-LTS-2)' >&2
+    /// # echo 'Java HotSpot(TM) 64-Bit Server VM (build 17.0.4.1+1-LTS-2, mixed mode, sharing)' >&2
+    /// # "#;
+    /// # fs::write(bin.join("java"), script).unwrap();
+    /// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// let runtime = JavaRuntime::from_executable(&bin.join("java")).unwrap();
+    /// assert_eq!(runtime.get_version_string(), "17.0.4.1");
+    /// assert_eq!(runtime.get_full_build_string().unwrap(), "17.0.4.1+1-LTS-2");
+    /// # }
+    /// ```
+    pub fn get_full_build_string(&self) -> Option<String> {
+        if let Some(build) = self.version_info.as_ref().and_then(VersionInfo::get_build) {
+            return Some(build.to_string());
+        }
+        let output = CommandProber::new().probe(&self.path).ok()?;
+        Self::extract_full_build_string(&properties::strip_properties(&output))
+    }
+
+    /// Get the system properties of this runtime, like `java.home` and `java.vendor`
+    ///
+    /// It executes command `java -XshowSettings:properties -version` and parses the output.
+    /// Values of path lists like `java.library.path` are joined by the path separator of the runtime's os.
+    ///
+    /// # Errors
+    ///
+    /// * [`ErrorKind::JavaOutputFailed`] if failed to execute java
+    /// * [`ErrorKind::GettingJavaVersionFailed`] if java exited with a failure status
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// # let home = env::temp_dir().join("java-runtimes-doctest-properties/jdk-17");
+    /// # fs::create_dir_all(home.join("bin")).unwrap();
+    /// # let script = format!(r#"#!/bin/sh
+    /// # echo 'Property settings:' >&2
+    /// # echo '    java.home = {}' >&2
+    /// # echo '    java.library.path = /usr/java/packages/lib' >&2
+    /// # echo '        /usr/lib64' >&2
+    /// # echo '    java.vendor = Eclipse Adoptium' >&2
+    /// # echo '' >&2
+    /// # echo 'openjdk version "17.0.4.1" 2022-08-12' >&2
+    /// # "#, home.display());
+    /// # fs::write(home.join("bin/java"), script).unwrap();
+    /// # fs::set_permissions(home.join("bin/java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// let runtime = JavaRuntime::from_executable(&home.join("bin/java")).unwrap();
+    /// let properties = runtime.probe_properties().unwrap();
+    /// assert_eq!(properties["java.home"], home.display().to_string());
+    /// assert_eq!(properties["java.vendor"], "Eclipse Adoptium");
+    /// assert_eq!(properties["java.library.path"], "/usr/java/packages/lib:/usr/lib64");
+    /// # }
+    /// ```
+    pub fn probe_properties(&self) -> Result<HashMap<String, String>, Error> {
+        let output = Command::new(&self.path)
+            .arg("-Djava.awt.headless=true")
+            .arg("-XshowSettings:properties")
+            .arg("-version")
+            .output()
+            .map_err(|err| Error::new(ErrorKind::JavaOutputFailed(err)))?;
+        if !output.status.success() {
+            return Err(Error::new(ErrorKind::GettingJavaVersionFailed(
+                self.path.clone(),
+            )));
+        }
+        let path_separator = if self.is_windows() { ';' } else { ':' };
+        Ok(properties::parse_properties(
+            &prober::decode_output(&output.stderr),
+            path_separator,
+        ))
+    }
+
+    /// Check if java can start with the given options, like the ones in `JAVA_TOOL_OPTIONS`
+    ///
+    /// It executes command `java <opts> -version`. Some options are only accepted by some versions,
+    /// e.g. `-XX:+UseZGC` requires Java 11+, so java refuses to start with them.
+    ///
+    /// # Errors
+    ///
+    /// * [`ErrorKind::RejectedJvmOption`] if java failed to start. The rejected option is identified
+    ///   by finding it, or the flag name of a `-XX:` option, in the error output.
+    /// * [`ErrorKind::JavaOutputFailed`] if failed to execute java
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(unix)] {
+    /// # use std::os::unix::fs::PermissionsExt;
+    /// # use std::{env, fs};
+    /// use java_runtimes::error::ErrorKind;
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// # let bin = env::temp_dir().join("java-runtimes-doctest-validate-options/jdk-17/bin");
+    /// # fs::create_dir_all(&bin).unwrap();
+    /// # let script = r#"#!/bin/sh
+    /// # for arg in "$@"; do
+    /// #     case "$arg" in
+    /// #         -Djava.awt.headless=true|-Xmx*|--enable-preview) ;;
+    /// #         -version) echo 'openjdk version "17.0.4.1" 2022-08-18' >&2; exit 0 ;;
+    /// #         -XX:+*) echo "Unrecognized VM option '${arg#-XX:+}'" >&2; exit 1 ;;
+    /// #         *) echo "Unrecognized option: $arg" >&2; exit 1 ;;
+    /// #     esac
+    /// # done
+    /// # "#;
+    /// # fs::write(bin.join("java"), script).unwrap();
+    /// # fs::set_permissions(bin.join("java"), fs::Permissions::from_mode(0o755)).unwrap();
+    /// let runtime = JavaRuntime::from_executable(&bin.join("java")).unwrap();
+    /// assert!(runtime.validate_options(&["-Xmx2g", "--enable-preview"]).is_ok());
+    ///
+    /// let err = runtime.validate_options(&["-Xmx2g", "--foo"]).unwrap_err();
+    /// assert!(matches!(
+    ///     err.kind(),
+    ///     ErrorKind::RejectedJvmOption { option: Some(option), .. } if option == "--foo"
+    /// ));
+    ///
+    /// let err = runtime.validate_options(&["-XX:+NoSuchFlag"]).unwrap_err();
+    /// assert!(matches!(
+    ///     err.kind(),
+    ///     ErrorKind::RejectedJvmOption { option: Some(option), .. } if option == "-XX:+NoSuchFlag"
+    /// ));
+    /// # }
+    /// ```
+    pub fn validate_options(&self, opts: &[&str]) -> Result<(), Error> {
+        let output = Command::new(&self.path)
+            .args(opts)
+            .arg("-version")
+            .output()
+            .map_err(|err| Error::new(ErrorKind::JavaOutputFailed(err)))?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let option = opts.iter().find(|opt| {
+            let flag_name = opt
+                .strip_prefix("-XX:")
+                .map(|flag| flag.trim_start_matches(['+', '-']))
+                .map(|flag| flag.split('=').next().unwrap_or(flag))
+                .filter(|name| !name.is_empty());
+            stderr.contains(*opt) || flag_name.is_some_and(|name| stderr.contains(name))
+        });
+        Err(Error::new(ErrorKind::RejectedJvmOption {
+            option: option.map(|opt| opt.to_string()),
+            message: stderr.lines().next().unwrap_or_default().to_string(),
+        }))
+    }
+
+    /// Extract the full build string from the output of `java -version`
+    ///
+    /// The build string is read from the `Runtime Environment` line, since the VM line of
+    /// legacy versions has a different one like `25.333-b02`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let oracle = r#"java version "1.8.0_333"
+    /// Java(TM) SE Runtime Environment (build 1.8.0_333-b02)
+    /// Java HotSpot(TM) 64-Bit Server VM (build 25.333-b02, mixed mode)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_full_build_string(oracle).unwrap(), "1.8.0_333-b02");
+    ///
+    /// let openjdk = r#"openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK Runtime Environment (build 17.0.4.1+1)
+    /// OpenJDK 64-Bit Server VM (build 17.0.4.1+1, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_full_build_string(openjdk).unwrap(), "17.0.4.1+1");
+    ///
+    /// let runtime_line_first = r#"OpenJDK Runtime Environment (build 11.0.2+9, "vendor 11.0")
+    /// openjdk version "17.0.4.1" 2022-08-12
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_full_build_string(runtime_line_first).unwrap(), "11.0.2+9");
+    ///
+    /// assert_eq!(JavaRuntime::extract_full_build_string("openjdk version \"17.0.4.1\""), None);
+    /// ```
+    pub fn extract_full_build_string(version_output: &str) -> Option<String> {
+        Some(
+            Regex::new(Self::BUILD_PATTERN)
+                .unwrap()
+                .captures(version_output)?
+                .get(1)?
+                .as_str()
+                .to_string(),
+        )
+    }
+
+    /// Extract the vendor from the runtime line of `java -version` output
+    ///
+    /// * `Java(TM) SE Runtime Environment` is built by Oracle, so it's `"Oracle"`
+    /// * Otherwise it's the name before `Runtime Environment`, like `"OpenJDK"`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let openjdk = r#"openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK Runtime Environment (build 17.0.4.1+1)
+    /// OpenJDK 64-Bit Server VM (build 17.0.4.1+1, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_vendor(openjdk).unwrap(), "OpenJDK");
+    ///
+    /// let oracle = r#"java version "1.8.0_333"
+    /// Java(TM) SE Runtime Environment (build 1.8.0_333-b02)
+    /// Java HotSpot(TM) 64-Bit Server VM (build 25.333-b02, mixed mode)
+    /// "#;
+    /// assert_eq!(JavaRuntime::extract_vendor(oracle).unwrap(), "Oracle");
+    ///
+    /// assert_eq!(JavaRuntime::extract_vendor("openjdk version \"17.0.4.1\""), None);
+    /// ```
+    pub fn extract_vendor(version_output: &str) -> Option<String> {
+        let runtime_line = Self::extract_runtime_line(version_output)?;
+        let (name, _) = runtime_line.split_once("Runtime Environment")?;
+        match name.trim() {
+            "" => None,
+            "Java(TM) SE" => Some("Oracle".to_string()),
+            name => Some(name.to_string()),
+        }
+    }
+
+    /// Parse the version output into [`VersionInfo`], if it has more than the version
+    fn parse_version_info(version_output: &str) -> Option<VersionInfo> {
+        VersionInfo::parse(version_output)
+            .ok()
+            .filter(|info| info.get_runtime_name().is_some() || info.get_vm_name().is_some())
+    }
+
+    /// Get the line like `OpenJDK Runtime Environment Temurin-17.0.4.1+1 (build 17.0.4.1+1)` from `java -version` output
+    fn extract_runtime_line(version_output: &str) -> Option<String> {
+        version_output
+            .lines()
+            .map(str::trim)
+            .find(|line| line.contains("Runtime Environment"))
+            .map(str::to_string)
+    }
+
+    /// Check if the given path looks like a java executable file
+    ///
+    /// The file must exists.
+    ///
+    /// The given path must be `**/bin/java.exe` or `**/bin/javaw.exe` in windows, or `**/bin/java` or `**/bin/javaw` in unix
+    pub(crate) fn looks_like_java_executable_file(path: &Path) -> bool {
+        Self::looks_like_java_executable_file_named(path, &[])
+    }
+
+    /// Same as [`JavaRuntime::looks_like_java_executable_file`], but the file name can also be one of the given names, like `javaw.exe`
+    pub(crate) fn looks_like_java_executable_file_named(
+        path: &Path,
+        extra_names: &[OsString],
+    ) -> bool {
+        if !path.is_file() {
+            return false;
+        }
+        // to absolute
+        let path_absolute = match path.canonicalize() {
+            Ok(path) => path,
+            _ => return false,
+        };
+        // check file name
+        if let Some(file_name) = path_absolute.file_name() {
+            if file_name == Self::get_java_executable_name()
+                || file_name == Self::get_javaw_executable_name()
+                || extra_names.iter().any(|name| name == file_name)
+            {
+                // check parent name
+                if let Some(parent) = path_absolute.parent() {
+                    if let Some(dir_name) = parent.file_name() {
+                        if dir_name == "bin" {
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// # Examples
+    /// * `java.exe` (windows)
+    /// * `java` (linux)
+    fn get_java_executable_name() -> OsString {
+        let mut java_exe = OsString::from("java");
+        java_exe.push(env::consts::EXE_SUFFIX);
+        java_exe
+    }
+
+    /// The java executable file without a console window, which is used by GUI launchers
+    ///
+    /// # Examples
+    /// * `javaw.exe` (windows)
+    /// * `javaw` (linux)
+    pub(crate) fn get_javaw_executable_name() -> OsString {
+        let mut javaw_exe = OsString::from("javaw");
+        javaw_exe.push(env::consts::EXE_SUFFIX);
+        javaw_exe
+    }
+}
+#[cfg(feature = "serde")]
+impl Serialize for JavaRuntime {
+    /// Serialize the fields together with the parsed `major_version`
+    ///
+    /// The extra fields are ignored when deserializing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "1.8.0_333").unwrap();
+    /// let json = serde_json::to_value(&runtime).unwrap();
+    /// assert_eq!(json["version_string"], "1.8.0_333");
+    /// assert_eq!(json["major_version"], 8);
+    ///
+    /// let deserialized: JavaRuntime = serde_json::from_value(json).unwrap();
+    /// assert_eq!(deserialized, runtime);
+    /// ```
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("JavaRuntime", 7)?;
+        state.serialize_field("os", &self.os)?;
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("version_string", &self.version_string)?;
+        state.serialize_field("vendor", &self.vendor)?;
+        state.serialize_field("arch", &self.arch)?;
+        state.serialize_field("version_info", &self.version_info)?;
+        state.serialize_field("major_version", &self.get_major_version())?;
+        state.end()
+    }
+}
+
+impl Clone for JavaRuntime {
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let r1 = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
+    /// let r2 = r1.clone();
+    ///
+    /// assert_eq!(r1, r2);
+    /// ```
+    fn clone(&self) -> Self {
+        Self {
+            os: self.os.clone(),
+            path: self.path.clone(),
+            version_string: self.version_string.clone(),
+            vendor: self.vendor.clone(),
+            arch: self.arch.clone(),
+            version_info: self.version_info.clone(),
+        }
+    }
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let mut r1 = JavaRuntime::new("windows", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
+    /// let r2 = JavaRuntime::new("windows", r"D:\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
+    ///
+    /// r1.clone_from(&r2);
+    /// assert_eq!(r1, r2);
+    /// ```
+    fn clone_from(&mut self, source: &Self) {
+        self.os = source.os.clone();
+        self.path = source.path.clone();
+        self.version_string = source.version_string.clone();
+        self.vendor = source.vendor.clone();
+        self.arch = source.arch.clone();
+        self.version_info = source.version_info.clone();
+    }
+}
+
+impl PartialEq for JavaRuntime {
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let r1 = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
+    /// let r2 = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
+    /// let r3 = JavaRuntime::new("windows", r"D:\jdk\bin\java.exe".as_ref(), "21.0.3").unwrap();
     /// let r4 = JavaRuntime::new("windows", r"D:\jdk-17\bin\java.exe".as_ref(), "21.0.3").unwrap();
     ///
     /// assert_eq!(r1, r2);
@@ -347,8 +2308,212 @@ impl PartialEq for JavaRuntime {
     fn eq(&self, other: &Self) -> bool {
         self.os == other.os && self.path == other.path
     }
+}
+
+impl Display for JavaRuntime {
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    ///
+    /// let runtime = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// assert_eq!(runtime.to_string(), "Java 17.0.4.1 (linux) at /jdk/bin/java");
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Java {} ({}) at {}",
+            self.version_string,
+            self.os,
+            self.path.display()
+        )
+    }
+}
+
+impl Hash for JavaRuntime {
+    /// Hash the same fields as [`PartialEq`], which are `os` and `path`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::JavaRuntime;
+    /// use std::collections::HashSet;
+    ///
+    /// let r1 = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "17.0.4.1").unwrap();
+    /// let r2 = JavaRuntime::new("linux", "/jdk/bin/java".as_ref(), "21.0.3").unwrap();
+    /// let r3 = JavaRuntime::new("linux", "/jdk-21/bin/java".as_ref(), "21.0.3").unwrap();
+    ///
+    /// let set = HashSet::from([r1.clone(), r2, r3]);
+    /// assert_eq!(set.len(), 2);
+    /// assert!(set.contains(&r1));
+    /// ```
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.os.hash(state);
+        self.path.hash(state);
+    }
+}
+
+impl Eq for JavaRuntime {}
+
+/// Get `(major, minor, patch, update)` numbers of the version string
+///
+/// The legacy scheme `1.8.0_333` is normalized to `(8, 0, 0, 333)`.
+fn version_key(version_string: &str) -> (u32, u32, u32, u32) {
+    let core = version_string.split(['+', '-']).next().unwrap_or_default();
+    let numbers = core
+        .split(['.', '_'])
+        .map(|s| s.parse::<u32>().unwrap_or(0))
+        .collect::<Vec<u32>>();
+    let number = |i: usize| numbers.get(i).copied().unwrap_or(0);
+    if number(0) == 1 && numbers.len() > 1 {
+        (number(1), 0, number(2), number(3))
+    } else {
+        (number(0), number(1), number(2), number(3))
+    }
+}
+
+/// Remove the verbatim prefix that [`Path::canonicalize`] adds on Windows, like `\\?\C:\jdk` to `C:\jdk`
+///
+/// Most tools, including batch scripts, don't accept verbatim paths.
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    let string = path.to_string_lossy();
+    if let Some(unc) = string.strip_prefix(r"\\?\UNC\") {
+        PathBuf::from(format!(r"\\{}", unc))
+    } else if let Some(disk) = string
+        .strip_prefix(r"\\?\")
+        .filter(|disk| disk.as_bytes().get(1) == Some(&b':'))
+    {
+        PathBuf::from(disk)
+    } else {
+        path
+    }
+}
+
+/// Map the different names of an architecture to the one used by [`env::consts::ARCH`]
+///
+/// For example, java reports `amd64` while rust says `x86_64`.
+fn normalize_arch(arch: &str) -> &str {
+    match arch {
+        "amd64" | "x64" => "x86_64",
+        "i386" | "i486" | "i586" | "i686" => "x86",
+        "arm64" => "aarch64",
+        arch => arch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use crate::fixture::Fixture;
+
+    #[test]
+    fn strip_verbatim_prefix_of_windows_paths() {
+        let strip = |path: &str| strip_verbatim_prefix(PathBuf::from(path));
+        assert_eq!(strip(r"\\?\C:\jdk"), PathBuf::from(r"C:\jdk"));
+        assert_eq!(
+            strip(r"\\?\UNC\server\share\jdk"),
+            PathBuf::from(r"\\server\share\jdk")
+        );
+        assert_eq!(
+            strip(r"\\?\Volume{1234}\jdk"),
+            PathBuf::from(r"\\?\Volume{1234}\jdk")
+        );
+        assert_eq!(strip(r"C:\jdk"), PathBuf::from(r"C:\jdk"));
+        assert_eq!(
+            strip("/usr/lib/jvm/jdk-17"),
+            PathBuf::from("/usr/lib/jvm/jdk-17")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn update_reports_broken_version_output() {
+        let fixture = Fixture::new();
+        let java = |home: &str, script: &str| fixture.java_script(home, script).join("bin/java");
+
+        let err = JavaRuntime::from_executable(&java("silent", "#!/bin/sh\n")).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::EmptyVersionOutput(_)));
+
+        let garbage = java("garbage", "#!/bin/sh\necho 'no version here' >&2\n");
+        let err = JavaRuntime::from_executable(&garbage).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NoJavaVersionStringFound));
+
+        // Left by a failed download
+        let err = JavaRuntime::from_executable(&java("truncated", "")).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::CorruptExecutable(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn update_decodes_utf16_output() {
+        let fixture = Fixture::new();
+        // Some Windows consoles get UTF-16 output with a byte order mark
+        let output = "openjdk version \"17.0.4.1\" 2022-08-18\r\n";
+        let mut utf16 = vec![0xff, 0xfe];
+        utf16.extend(output.encode_utf16().flat_map(u16::to_le_bytes));
+        let output_file = fixture.root().join("utf16-output");
+        fs::write(&output_file, utf16).unwrap();
+        let script = format!("#!/bin/sh\ncat '{}' >&2\n", output_file.display());
+
+        let java = fixture.java_script("utf16", &script).join("bin/java");
+        let runtime = JavaRuntime::from_executable(&java).unwrap();
+        assert_eq!(runtime.get_version_string(), "17.0.4.1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn update_runs_java_in_headless_mode() {
+        let fixture = Fixture::new();
+        // Fails unless it runs in headless mode
+        let script = r#"#!/bin/sh
+[ "$1" = "-Djava.awt.headless=true" ] || exit 1
+echo 'openjdk version "17.0.4.1" 2022-08-18' >&2
+"#;
+        let java = fixture.java_script("headless", script).join("bin/java");
+        let runtime = JavaRuntime::from_executable(&java).unwrap();
+        assert_eq!(runtime.get_version_string(), "17.0.4.1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn is_usable_here_checks_the_arch() {
+        let fixture = Fixture::new();
+        let java = |home: &str, os_arch: &str| {
+            let home = fixture.java(home, "17.0.4.1");
+            fs::write(home.join("release"), format!("OS_ARCH=\"{}\"\n", os_arch)).unwrap();
+            home.join("bin/java")
+        };
+
+        let native = JavaRuntime::from_executable(&java("native", env::consts::ARCH)).unwrap();
+        assert!(native.is_usable_here());
+
+        let other_arch = if env::consts::ARCH == "aarch64" {
+            "x86_64"
+        } else {
+            "aarch64"
+        };
+        let foreign = JavaRuntime::from_executable(&java("foreign", other_arch)).unwrap();
+        assert!(foreign.is_available());
+        assert!(!foreign.is_usable_here());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn from_executable_with_env_sets_the_env() {
+        let mut fixture = Fixture::new();
+        // Fails unless `LANG=C`
+        let script = r#"#!/bin/sh
+[ "$LANG" = "C" ] || exit 1
+echo 'openjdk version "17.0.4.1" 2022-08-18' >&2
+"#;
+        let java = fixture.java_script("jdk", script).join("bin/java");
+
+        fixture.set_var("LANG", "en_US.UTF-8");
+        assert!(JavaRuntime::from_executable(&java).is_err());
 
-    fn ne(&self, other: &Self) -> bool {
-        !self.eq(other)
+        let env = [("LANG".to_string(), "C".to_string())];
+        let runtime = JavaRuntime::from_executable_with_env(&java, &env).unwrap();
+        assert_eq!(runtime.get_version_string(), "17.0.4.1");
     }
 }
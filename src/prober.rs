@@ -0,0 +1,277 @@
+//! This module provides the seam for getting the version output of java executable files.
+//!
+//! By default, [`CommandProber`] executes `java -version`. Other implementations of [`VersionProber`]
+//! can be injected, e.g. to test parsing and selection logic without real java runtimes.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use java_runtimes::error::Error;
+//! use java_runtimes::prober::VersionProber;
+//! use java_runtimes::JavaRuntime;
+//! use std::path::Path;
+//!
+//! #[derive(Debug)]
+//! struct MockProber;
+//!
+//! impl VersionProber for MockProber {
+//!     fn probe(&self, _executable: &Path) -> Result<String, Error> {
+//!         Ok("openjdk version \"17.0.4.1\" 2022-08-12".to_string())
+//!     }
+//! }
+//!
+//! let runtime = JavaRuntime::from_executable_with_prober("/jdk/bin/java".as_ref(), &MockProber).unwrap();
+//! assert_eq!(runtime.get_version_string(), "17.0.4.1");
+//! ```
+//!
+//! A prober returning canned outputs of several distributions, to update runtimes deterministically
+//!
+//! ```rust
+//! use java_runtimes::error::Error;
+//! use java_runtimes::prober::VersionProber;
+//! use java_runtimes::vendor::Vendor;
+//! use java_runtimes::JavaRuntime;
+//! use std::collections::HashMap;
+//! use std::path::{Path, PathBuf};
+//!
+//! #[derive(Debug)]
+//! struct CannedProber(HashMap<PathBuf, &'static str>);
+//!
+//! impl VersionProber for CannedProber {
+//!     fn probe(&self, executable: &Path) -> Result<String, Error> {
+//!         Ok(self.0.get(executable).copied().unwrap_or_default().to_string())
+//!     }
+//! }
+//!
+//! let prober = CannedProber(HashMap::from([
+//!     (
+//!         PathBuf::from("/jdk-8/bin/java"),
+//!         "java version \"1.8.0_333\"\nJava(TM) SE Runtime Environment (build 1.8.0_333-b02)\n",
+//!     ),
+//!     (
+//!         PathBuf::from("/jdk-11/bin/java"),
+//!         "openjdk version \"11.0.2\" 2019-01-15\nOpenJDK Runtime Environment 18.9 (build 11.0.2+9)\n",
+//!     ),
+//!     (
+//!         PathBuf::from("/jdk-21/bin/java"),
+//!         "openjdk version \"21.0.1\" 2023-10-17 LTS\nOpenJDK Runtime Environment Corretto-21.0.1.12.1 (build 21.0.1+12-LTS)\n",
+//!     ),
+//! ]));
+//!
+//! let mut runtime = JavaRuntime::new("linux", "/jdk-8/bin/java".as_ref(), "1.8.0_1").unwrap();
+//! runtime.update_with_prober(&prober).unwrap();
+//! assert_eq!(runtime.get_major_version(), 8);
+//! assert_eq!(runtime.get_vendor(), Some("Oracle"));
+//!
+//! let runtime = JavaRuntime::from_executable_with_prober("/jdk-11/bin/java".as_ref(), &prober).unwrap();
+//! assert_eq!(runtime.get_version_string(), "11.0.2");
+//!
+//! let runtime = JavaRuntime::from_executable_with_prober("/jdk-21/bin/java".as_ref(), &prober).unwrap();
+//! assert_eq!(runtime.get_version_string(), "21.0.1");
+//! assert_eq!(runtime.classify_vendor(), Vendor::Corretto);
+//!
+//! // Nothing printed
+//! assert!(JavaRuntime::from_executable_with_prober("/jre/bin/java".as_ref(), &prober).is_err());
+//! ```
+
+use crate::error::{Error, ErrorKind};
+use crate::JavaRuntime;
+use std::ffi::OsString;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+/// Trait [`VersionProber`] gets the version output of a java executable file.
+pub trait VersionProber: Debug + Send + Sync {
+    /// Get the output of `java -version` from the given java executable file
+    ///
+    /// The output is parsed by [`JavaRuntime::extract_version`]. It may start with the system properties
+    /// printed by `java -XshowSettings:properties -version`, which are parsed for the architecture.
+    fn probe(&self, executable: &Path) -> Result<String, Error>;
+}
+
+/// Struct [`CommandProber`] executes `java -version` to get the version output.
+///
+/// Java is executed with `-Djava.awt.headless=true`, so that JVMs trying to initialize AWT
+/// don't fail on machines without a display.
+///
+/// The system properties are printed before the version with `-XshowSettings:properties`,
+/// so that the architecture is known, see [`JavaRuntime::get_arch`]. If java rejects the option,
+/// it's executed again with `-version` only. Java failing for other reasons is not executed again.
+///
+/// # Examples
+///
+/// ```rust
+/// use java_runtimes::prober::{CommandProber, VersionProber};
+///
+/// if let Ok(output) = CommandProber::new().probe("/usr/lib/jvm/jdk-17/bin/java".as_ref()) {
+///     println!("{}", output);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CommandProber {
+    env: Vec<(String, String)>,
+    executable_names: Vec<OsString>,
+}
+
+impl CommandProber {
+    /// Create a prober executing java with the environment variables of current process
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a prober executing java with extra environment variables, as `(key, value)` pairs
+    pub fn with_env(env: &[(String, String)]) -> Self {
+        Self {
+            env: env.to_vec(),
+            ..Self::default()
+        }
+    }
+
+    /// Accept another name of java executable file besides `java(.exe)` and `javaw(.exe)`
+    pub fn executable_name(mut self, name: impl Into<OsString>) -> Self {
+        self.executable_names.push(name.into());
+        self
+    }
+}
+
+impl VersionProber for CommandProber {
+    /// # Errors
+    ///
+    /// * [`ErrorKind::LooksNotLikeJavaExecutableFile`] if the path is not like `**/bin/java(.exe)` or `**/bin/javaw(.exe)`, or another accepted name
+    /// * [`ErrorKind::CorruptExecutable`] if the file is empty or does not start with a known executable format
+    /// * [`ErrorKind::JavaOutputFailed`] if failed to execute it
+    /// * [`ErrorKind::GettingJavaVersionFailed`] if it exited with a failure status
+    fn probe(&self, executable: &Path) -> Result<String, Error> {
+        if !JavaRuntime::looks_like_java_executable_file_named(executable, &self.executable_names) {
+            return Err(Error::new(ErrorKind::LooksNotLikeJavaExecutableFile(
+                executable.to_path_buf(),
+            )));
+        }
+        if !has_executable_magic(executable) {
+            return Err(Error::new(ErrorKind::CorruptExecutable(
+                executable.to_path_buf(),
+            )));
+        }
+
+        let run = |args: &[&str]| {
+            Command::new(executable)
+                .arg("-Djava.awt.headless=true")
+                .args(args)
+                .envs(self.env.iter().map(|(key, value)| (key, value)))
+                .output()
+                .map_err(|err| Error::new(ErrorKind::JavaOutputFailed(err)))
+        };
+        let mut output = run(&["-XshowSettings:properties", "-version"])?;
+        // `-XshowSettings` is not supported before Java 7
+        if !output.status.success() && decode_output(&output.stderr).contains("-XshowSettings") {
+            output = run(&["-version"])?;
+        }
+
+        if output.status.success() {
+            Ok(decode_output(&output.stderr))
+        } else {
+            Err(Error::new(ErrorKind::GettingJavaVersionFailed(
+                executable.to_path_buf(),
+            )))
+        }
+    }
+}
+
+/// Magic numbers at the start of executable files
+///
+/// * ELF
+/// * PE (`MZ`)
+/// * Mach-O, 32/64-bit in both byte orders, and universal binaries
+/// * Scripts with a shebang line (`#!`), like wrappers of some distributions
+const EXECUTABLE_MAGICS: [&[u8]; 8] = [
+    b"\x7fELF",
+    b"MZ",
+    b"\xfe\xed\xfa\xce",
+    b"\xfe\xed\xfa\xcf",
+    b"\xce\xfa\xed\xfe",
+    b"\xcf\xfa\xed\xfe",
+    b"\xca\xfe\xba\xbe",
+    b"#!",
+];
+
+/// Check if the file starts with a known executable format
+///
+/// Returns `false` for an empty file, which is common after a failed download.
+fn has_executable_magic(path: &Path) -> bool {
+    let mut header = [0u8; 4];
+    let Ok(read) = File::open(path).and_then(|mut file| file.read(&mut header)) else {
+        // Leave unreadable files to the execution, which reports the real reason
+        return true;
+    };
+    EXECUTABLE_MAGICS
+        .iter()
+        .any(|magic| header[..read].starts_with(magic))
+}
+
+/// Decode the output of java into a string
+///
+/// Java on some Windows consoles writes UTF-16 with a byte order mark, which is decoded accordingly.
+/// Otherwise the output is decoded as UTF-8, with invalid sequences replaced.
+pub(crate) fn decode_output(bytes: &[u8]) -> String {
+    let utf16 = |rest: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units = rest
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect::<Vec<u16>>();
+        String::from_utf16_lossy(&units)
+    };
+    if let Some(rest) = bytes.strip_prefix(b"\xff\xfe") {
+        utf16(rest, u16::from_le_bytes)
+    } else if let Some(rest) = bytes.strip_prefix(b"\xfe\xff") {
+        utf16(rest, u16::from_be_bytes)
+    } else {
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::fixture::Fixture;
+    use std::fs;
+
+    #[test]
+    fn command_prober_retries_only_when_the_option_is_rejected() {
+        let fixture = Fixture::new();
+        let runs = fixture.root().join("runs");
+        let java_6 = format!(
+            r#"#!/bin/sh
+echo run >> '{}'
+if [ "$2" = "-XshowSettings:properties" ]; then
+    echo 'Unrecognized option: -XshowSettings:properties' >&2
+    exit 1
+fi
+echo 'java version "1.6.0_45"' >&2
+"#,
+            runs.display()
+        );
+        let java_6 = fixture.java_script("jdk-6", &java_6).join("bin/java");
+        let broken = format!(
+            r#"#!/bin/sh
+echo run >> '{}'
+echo 'Error: could not find libjava.so' >&2
+exit 1
+"#,
+            runs.display()
+        );
+        let broken = fixture.java_script("broken", &broken).join("bin/java");
+
+        // Executed again without the option
+        let output = CommandProber::new().probe(&java_6).unwrap();
+        assert!(output.contains("1.6.0_45"));
+        assert_eq!(fs::read_to_string(&runs).unwrap().lines().count(), 2);
+
+        // Executed only once
+        fs::remove_file(&runs).unwrap();
+        assert!(CommandProber::new().probe(&broken).is_err());
+        assert_eq!(fs::read_to_string(&runs).unwrap().lines().count(), 1);
+    }
+}
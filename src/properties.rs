@@ -0,0 +1,45 @@
+//! Parsing of the system properties printed by `java -XshowSettings:properties -version`.
+//!
+//! The output consists of lines like `    java.home = /usr/lib/jvm/java-17-openjdk`.
+//! Values of path lists are split into multiple lines, each following line is indented deeper:
+//!
+//! ```text
+//! Property settings:
+//!     java.library.path = /usr/java/packages/lib
+//!         /usr/lib64
+//!         /lib64
+//! ```
+
+use std::collections::HashMap;
+
+/// Parse the output into key-value pairs, with the lines of a path list joined by the given separator
+pub(crate) fn parse_properties(output: &str, path_separator: char) -> HashMap<String, String> {
+    let mut properties: HashMap<String, String> = HashMap::new();
+    let mut last_key: Option<String> = None;
+    for line in output.lines() {
+        if line.starts_with("        ") {
+            if let Some(value) = last_key.as_ref().and_then(|key| properties.get_mut(key)) {
+                value.push(path_separator);
+                value.push_str(line.trim());
+            }
+        } else if let Some((key, value)) = line
+            .strip_prefix("    ")
+            .and_then(|line| line.split_once(" = "))
+        {
+            properties.insert(key.trim().to_string(), value.trim().to_string());
+            last_key = Some(key.trim().to_string());
+        } else {
+            last_key = None;
+        }
+    }
+    properties
+}
+
+/// Remove the property settings from the output, leaving the lines printed by `java -version`
+pub(crate) fn strip_properties(output: &str) -> String {
+    output
+        .lines()
+        .filter(|line| !line.starts_with("    ") && *line != "Property settings:")
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
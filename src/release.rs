@@ -0,0 +1,32 @@
+//! Parsing of the `release` file in java home.
+//!
+//! The file consists of lines like `JAVA_VERSION="17.0.4.1"`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Read and parse the given `release` file
+///
+/// Returns `None` if the file can not be read.
+pub(crate) fn read_release(release_file: &Path) -> Option<HashMap<String, String>> {
+    let content = fs::read_to_string(release_file).ok()?;
+    Some(parse_release(&content))
+}
+
+/// Parse the content of a `release` file into key-value pairs, with quotes around the values removed
+pub(crate) fn parse_release(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .filter(|(key, _)| !key.is_empty() && !key.starts_with('#'))
+        .collect()
+}
@@ -0,0 +1,138 @@
+//! This module provides [`DetectionReport`], a snapshot of detected java runtimes that can be saved and compared later.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use java_runtimes::detector;
+//! use java_runtimes::report::DetectionReport;
+//!
+//! let report = DetectionReport::new(detector::detect_java_in_environments());
+//! println!("Detected Java runtimes: {:?}", report.get_runtimes());
+//! ```
+
+use crate::JavaRuntime;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Struct [`DetectionReport`] holds the java runtimes detected at some time.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DetectionReport {
+    runtimes: Vec<JavaRuntime>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    scan_duration: Duration,
+    #[cfg_attr(feature = "serde", serde(default))]
+    dirs_scanned: usize,
+}
+
+impl DetectionReport {
+    /// Create a report from detected runtimes, without statistics of the scan
+    ///
+    /// To detect runtimes and get a report with statistics, see [`detector::detect_report`](crate::detector::detect_report)
+    pub fn new(runtimes: Vec<JavaRuntime>) -> Self {
+        Self::with_stats(runtimes, Duration::ZERO, 0)
+    }
+
+    pub(crate) fn with_stats(
+        runtimes: Vec<JavaRuntime>,
+        scan_duration: Duration,
+        dirs_scanned: usize,
+    ) -> Self {
+        Self {
+            runtimes,
+            scan_duration,
+            dirs_scanned,
+        }
+    }
+
+    /// Get the detected runtimes
+    pub fn get_runtimes(&self) -> &[JavaRuntime] {
+        &self.runtimes
+    }
+
+    /// Get how long the detection took
+    pub fn get_scan_duration(&self) -> Duration {
+        self.scan_duration
+    }
+
+    /// Get the number of directories visited during the detection
+    pub fn get_dirs_scanned(&self) -> usize {
+        self.dirs_scanned
+    }
+
+    /// Find the paths that resolve to a different java version in the current report than in this one.
+    ///
+    /// A path like `/usr/lib/jvm/java-17` is an alias which may point to another installation after an upgrade.
+    /// Paths present in only one of the reports are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::report::DetectionReport;
+    /// use java_runtimes::JavaRuntime;
+    /// use std::path::Path;
+    ///
+    /// let alias = Path::new("/usr/lib/jvm/java-17/bin/java");
+    /// let other = Path::new("/usr/lib/jvm/java-11/bin/java");
+    ///
+    /// let previous = DetectionReport::new(vec![
+    ///     JavaRuntime::new("linux", alias, "17.0.4").unwrap(),
+    ///     JavaRuntime::new("linux", other, "11.0.2").unwrap(),
+    /// ]);
+    /// let current = DetectionReport::new(vec![
+    ///     JavaRuntime::new("linux", alias, "17.0.8").unwrap(),
+    ///     JavaRuntime::new("linux", other, "11.0.2").unwrap(),
+    /// ]);
+    ///
+    /// let changes = previous.changed_aliases(&current);
+    /// assert_eq!(changes.len(), 1);
+    /// assert_eq!(changes[0].get_path(), alias);
+    /// assert_eq!(changes[0].get_previous_version(), "17.0.4");
+    /// assert_eq!(changes[0].get_current_version(), "17.0.8");
+    /// ```
+    pub fn changed_aliases(&self, current: &DetectionReport) -> Vec<AliasChange> {
+        self.runtimes
+            .iter()
+            .filter_map(|previous| {
+                let current = current.runtimes.iter().find(|r| *r == previous)?;
+                if current.get_version_string() == previous.get_version_string() {
+                    return None;
+                }
+                Some(AliasChange {
+                    path: previous.get_executable().to_path_buf(),
+                    previous_version: previous.get_version_string().to_string(),
+                    current_version: current.get_version_string().to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Struct [`AliasChange`] represents a java executable path whose version changed between two reports.
+///
+/// See [`DetectionReport::changed_aliases`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasChange {
+    path: PathBuf,
+    previous_version: String,
+    current_version: String,
+}
+
+impl AliasChange {
+    /// Get the path of java executable file
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the version string in the previous report
+    pub fn get_previous_version(&self) -> &str {
+        &self.previous_version
+    }
+
+    /// Get the version string in the current report
+    pub fn get_current_version(&self) -> &str {
+        &self.current_version
+    }
+}
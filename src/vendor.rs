@@ -0,0 +1,110 @@
+//! This module provides [`Vendor`], the normalized vendor of a java runtime.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use java_runtimes::vendor::Vendor;
+//!
+//! let vendor = Vendor::classify("OpenJDK Runtime Environment Corretto-17.0.4.9.1 (build 17.0.4.1+9-LTS)");
+//! assert_eq!(vendor, Vendor::Corretto);
+//! ```
+
+/// Enum [`Vendor`] represents the distribution of a java runtime.
+///
+/// See [`JavaRuntime::classify_vendor`](crate::JavaRuntime::classify_vendor)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Vendor {
+    /// Oracle JDK, whose runtime is `Java(TM) SE Runtime Environment`
+    Oracle,
+    /// OpenJDK builds without a distribution name, like the ones from Linux distributions
+    OpenJdk,
+    /// Eclipse Temurin, formerly AdoptOpenJDK
+    Temurin,
+    /// Amazon Corretto
+    Corretto,
+    /// Azul Zulu
+    Zulu,
+    /// GraalVM, either Oracle GraalVM or GraalVM Community Edition
+    GraalVm,
+    /// BellSoft Liberica
+    Liberica,
+    /// Microsoft Build of OpenJDK
+    Microsoft,
+    /// Other vendors, with the name before `Runtime Environment`, or the whole line if it's absent
+    Other(String),
+}
+
+impl Vendor {
+    /// Distribution names that may appear in the runtime line, and the vendors they indicate
+    ///
+    /// GraalVM comes first, since Oracle GraalVM is also `Java(TM) SE`.
+    const KNOWN_NAMES: [(&'static str, Vendor); 8] = [
+        ("GraalVM", Vendor::GraalVm),
+        ("Temurin", Vendor::Temurin),
+        ("AdoptOpenJDK", Vendor::Temurin),
+        ("Corretto", Vendor::Corretto),
+        ("Zulu", Vendor::Zulu),
+        ("Liberica", Vendor::Liberica),
+        ("BellSoft", Vendor::Liberica),
+        ("Microsoft", Vendor::Microsoft),
+    ];
+
+    /// Classify the vendor from the runtime line of `java -version` output, or the whole output
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::vendor::Vendor;
+    ///
+    /// let corretto = r#"openjdk version "17.0.4.1" 2022-08-12 LTS
+    /// OpenJDK Runtime Environment Corretto-17.0.4.9.1 (build 17.0.4.1+9-LTS)
+    /// OpenJDK 64-Bit Server VM Corretto-17.0.4.9.1 (build 17.0.4.1+9-LTS, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(Vendor::classify(corretto), Vendor::Corretto);
+    ///
+    /// let zulu = r#"openjdk version "17.0.4" 2022-07-19 LTS
+    /// OpenJDK Runtime Environment Zulu17.36+17-CA (build 17.0.4+8-LTS)
+    /// OpenJDK 64-Bit Server VM Zulu17.36+17-CA (build 17.0.4+8-LTS, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(Vendor::classify(zulu), Vendor::Zulu);
+    ///
+    /// let graalvm = r#"java version "21.0.1" 2023-10-17
+    /// Java(TM) SE Runtime Environment Oracle GraalVM 21.0.1+12.1 (build 21.0.1+12-jvmci-23.1-b19)
+    /// Java HotSpot(TM) 64-Bit Server VM Oracle GraalVM 21.0.1+12.1 (build 21.0.1+12-jvmci-23.1-b19, mixed mode, sharing)
+    /// "#;
+    /// assert_eq!(Vendor::classify(graalvm), Vendor::GraalVm);
+    ///
+    /// let oracle = r#"java version "1.8.0_333"
+    /// Java(TM) SE Runtime Environment (build 1.8.0_333-b02)
+    /// "#;
+    /// assert_eq!(Vendor::classify(oracle), Vendor::Oracle);
+    ///
+    /// let openjdk = "OpenJDK Runtime Environment (build 17.0.15+6-Debian-1deb12u1)";
+    /// assert_eq!(Vendor::classify(openjdk), Vendor::OpenJdk);
+    ///
+    /// let other = "Acme Runtime Environment (build 17.0.4+1)";
+    /// assert_eq!(Vendor::classify(other), Vendor::Other("Acme".to_string()));
+    /// ```
+    pub fn classify(version_output: &str) -> Vendor {
+        let line = version_output
+            .lines()
+            .map(str::trim)
+            .find(|line| line.contains("Runtime Environment"))
+            .unwrap_or(version_output.trim());
+        if let Some((_, vendor)) = Self::KNOWN_NAMES
+            .iter()
+            .find(|(name, _)| line.contains(name))
+        {
+            return vendor.clone();
+        }
+        let name = line
+            .split_once("Runtime Environment")
+            .map_or(line, |(name, _)| name)
+            .trim();
+        match name {
+            "Java(TM) SE" => Vendor::Oracle,
+            "OpenJDK" => Vendor::OpenJdk,
+            name => Vendor::Other(name.to_string()),
+        }
+    }
+}
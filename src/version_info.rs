@@ -0,0 +1,154 @@
+//! This module provides [`VersionInfo`], the structured `java -version` output.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use java_runtimes::version_info::VersionInfo;
+//!
+//! let output = r#"openjdk version "17.0.4.1" 2022-08-12
+//! OpenJDK Runtime Environment (build 17.0.4.1+1)
+//! OpenJDK 64-Bit Server VM (build 17.0.4.1+1, mixed mode, sharing)
+//! "#;
+//! let info = VersionInfo::parse(output).unwrap();
+//! assert_eq!(info.get_version(), "17.0.4.1");
+//! assert_eq!(info.get_vm_mode(), Some("mixed mode"));
+//! ```
+
+use crate::error::Error;
+use crate::JavaRuntime;
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Struct [`VersionInfo`] holds the fields of the three-line `java -version` output.
+///
+/// ```text
+/// java version "1.8.0_333"                                          <- version
+/// Java(TM) SE Runtime Environment (build 1.8.0_333-b02)             <- runtime name, build
+/// Java HotSpot(TM) 64-Bit Server VM (build 25.333-b02, mixed mode)  <- vm name, vm mode
+/// ```
+///
+/// See [`JavaRuntime::get_version_info`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VersionInfo {
+    version: String,
+    build: Option<String>,
+    runtime_name: Option<String>,
+    vm_name: Option<String>,
+    vm_mode: Option<String>,
+}
+
+impl VersionInfo {
+    /// Used to match the VM line like `OpenJDK 64-Bit Server VM (build 17.0.4.1+1, mixed mode, sharing)`
+    ///
+    /// Captures the name, the build string and the first detail after it.
+    const BUILD_LINE_PATTERN: &'static str = r"^(.+?)\s*\(build ([^,)\s]+)(?:, ([^,)]+))?";
+
+    /// Parse the output of `java -version`
+    ///
+    /// Only the version is required, other fields are `None` if the lines are absent.
+    ///
+    /// # Errors
+    ///
+    /// * [`ErrorKind::NoJavaVersionStringFound`](crate::error::ErrorKind::NoJavaVersionStringFound) if the output contains no version
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use java_runtimes::version_info::VersionInfo;
+    ///
+    /// let oracle = r#"java version "1.8.0_333"
+    /// Java(TM) SE Runtime Environment (build 1.8.0_333-b02)
+    /// Java HotSpot(TM) 64-Bit Server VM (build 25.333-b02, mixed mode)
+    /// "#;
+    /// let info = VersionInfo::parse(oracle).unwrap();
+    /// assert_eq!(info.get_version(), "1.8.0_333");
+    /// assert_eq!(info.get_build(), Some("1.8.0_333-b02"));
+    /// assert_eq!(info.get_runtime_name(), Some("Java(TM) SE Runtime Environment"));
+    /// assert_eq!(info.get_vm_name(), Some("Java HotSpot(TM) 64-Bit Server VM"));
+    /// assert_eq!(info.get_vm_mode(), Some("mixed mode"));
+    ///
+    /// let openjdk = r#"openjdk version "17.0.4.1" 2022-08-12
+    /// OpenJDK Runtime Environment (build 17.0.4.1+1)
+    /// OpenJDK 64-Bit Server VM (build 17.0.4.1+1, interpreted mode, sharing)
+    /// "#;
+    /// let info = VersionInfo::parse(openjdk).unwrap();
+    /// assert_eq!(info.get_version(), "17.0.4.1");
+    /// assert_eq!(info.get_build(), Some("17.0.4.1+1"));
+    /// assert_eq!(info.get_runtime_name(), Some("OpenJDK Runtime Environment"));
+    /// assert_eq!(info.get_vm_name(), Some("OpenJDK 64-Bit Server VM"));
+    /// assert_eq!(info.get_vm_mode(), Some("interpreted mode"));
+    ///
+    /// let graalvm = r#"java version "21.0.1" 2023-10-17
+    /// Java(TM) SE Runtime Environment Oracle GraalVM 21.0.1+12.1 (build 21.0.1+12-jvmci-23.1-b19)
+    /// Java HotSpot(TM) 64-Bit Server VM Oracle GraalVM 21.0.1+12.1 (build 21.0.1+12-jvmci-23.1-b19, mixed mode, sharing)
+    /// "#;
+    /// let info = VersionInfo::parse(graalvm).unwrap();
+    /// assert_eq!(info.get_version(), "21.0.1");
+    /// assert_eq!(info.get_build(), Some("21.0.1+12-jvmci-23.1-b19"));
+    /// assert_eq!(info.get_runtime_name(), Some("Java(TM) SE Runtime Environment Oracle GraalVM 21.0.1+12.1"));
+    /// assert_eq!(info.get_vm_name(), Some("Java HotSpot(TM) 64-Bit Server VM Oracle GraalVM 21.0.1+12.1"));
+    /// assert_eq!(info.get_vm_mode(), Some("mixed mode"));
+    ///
+    /// // Only the first line
+    /// let info = VersionInfo::parse("openjdk version \"17.0.4.1\" 2022-08-12").unwrap();
+    /// assert_eq!(info.get_build(), None);
+    /// assert_eq!(info.get_vm_mode(), None);
+    ///
+    /// assert!(VersionInfo::parse("command not found").is_err());
+    /// ```
+    pub fn parse(stderr: &str) -> Result<VersionInfo, Error> {
+        let version = JavaRuntime::extract_version(stderr)?;
+        let runtime_name = JavaRuntime::extract_runtime_line(stderr).map(|line| {
+            line.split_once("(build")
+                .map_or(line.as_str(), |(name, _)| name)
+                .trim()
+                .to_string()
+        });
+        let pattern = Regex::new(Self::BUILD_LINE_PATTERN).unwrap();
+        let vm_line = stderr
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.contains("Runtime Environment"))
+            .find_map(|line| pattern.captures(line));
+        let vm_capture = |group: usize| {
+            vm_line
+                .as_ref()
+                .and_then(|captures| captures.get(group))
+                .map(|value| value.as_str().trim().to_string())
+        };
+        Ok(VersionInfo {
+            version,
+            build: JavaRuntime::extract_full_build_string(stderr),
+            runtime_name,
+            vm_name: vm_capture(1),
+            vm_mode: vm_capture(3),
+        })
+    }
+
+    /// Get the version, like `17.0.4.1`
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    /// Get the build string of the runtime, like `17.0.4.1+1-LTS-2`
+    pub fn get_build(&self) -> Option<&str> {
+        self.build.as_deref()
+    }
+
+    /// Get the runtime name, like `OpenJDK Runtime Environment`
+    pub fn get_runtime_name(&self) -> Option<&str> {
+        self.runtime_name.as_deref()
+    }
+
+    /// Get the VM name, like `OpenJDK 64-Bit Server VM`
+    pub fn get_vm_name(&self) -> Option<&str> {
+        self.vm_name.as_deref()
+    }
+
+    /// Get the VM mode, like `mixed mode` or `interpreted mode`
+    pub fn get_vm_mode(&self) -> Option<&str> {
+        self.vm_mode.as_deref()
+    }
+}